@@ -1,200 +1,333 @@
-use crate::nodes::node::{Node, NodeTraits};
+use crate::{
+    nodes::node::{Node, NodeTraits, SupportType},
+    solvers::{cholesky, error::SolverError},
+};
 
-struct Element {
-    nodes: [usize; 2], // IDs of the nodes forming the element
-    length: f64,
-    material_properties: MaterialProperties,
+// Degrees of freedom per node: sway rightward, deflection downward and rotation clockwise
+const DOFS: usize = 3;
+
+// Two-node beam-column element, stiff axially, in shear and in bending
+#[derive(Debug, Clone, Copy)]
+pub struct Element {
+    pub nodes: [usize; 2], // IDs of the nodes forming the element
+    pub material_properties: MaterialProperties,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct MaterialProperties {
+    pub modulus_of_elasticity: f64,
+    pub moment_of_inertia: f64,
+    pub area: f64,
+}
+
+// End actions of an element in its own axes, with the member end conventions of the spans
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ElementForces {
+    // Axial force at each end, tension positive
+    pub axial: [f64; 2],
+    // Force square to the element exerted on each end, against its clockwise side
+    pub shear: [f64; 2],
+    // Member End Moments, clockwise positive
+    pub moment: [f64; 2],
 }
 
-struct Frame {
+// Plane frame analysed by the direct stiffness method, with the axial shortening of its members
+#[derive(Debug, Clone)]
+pub struct Frame {
     nodes: Vec<Node>,
     elements: Vec<Element>,
-    loads: Vec<(usize, f64)>, // (node_id, magnitude) of loads
-    supports: Vec<usize>, // IDs of supported nodes
-    material_properties: MaterialProperties,
+    loads: Vec<(usize, [f64; 3])>, // (node_id, [horizontal, vertical, moment]) of loads
 }
 
-struct MaterialProperties {
-    modulus_of_elasticity: f64,
-    moment_of_inertia: f64,
+// Which of the sway, deflection and rotation of a node the support holds
+fn restraints(support: SupportType) -> [bool; DOFS] {
+    match support {
+        SupportType::Fixed => [true, true, true],
+        SupportType::Hinged => [true, true, false],
+        SupportType::Roller => [false, true, false],
+        SupportType::Free => [false, false, false],
+        SupportType::Guided => [true, false, true],
+        SupportType::Spring { translational, rotational } => [false, translational.is_infinite(), rotational.is_infinite()],
+    }
+}
+
+impl Element {
+    fn length_and_direction(&self, nodes: &[Node]) -> (f64, [f64; 2]) {
+        let [start, end] = self.nodes.map(|id| nodes[id]);
+        let dx = end.get_x() - start.get_x();
+        let dy = end.get_y() - start.get_y();
+        let length = dx.hypot(dy);
+        (length, [dx / length, dy / length])
+    }
+
+    // Stiffness in the element axes, along it and square to it on its clockwise side
+    fn local_stiffness(&self, length: f64) -> [[f64; 6]; 6] {
+        let MaterialProperties { modulus_of_elasticity: e, moment_of_inertia: i, area: a } = self.material_properties;
+        let axial = e * a / length;
+        let ei = e * i;
+        let [k1, k2, k3, k4] = [12.0 * ei / length.powi(3), 6.0 * ei / length.powi(2), 4.0 * ei / length, 2.0 * ei / length];
+        [
+            [ axial, 0.0, 0.0, -axial, 0.0, 0.0],
+            [ 0.0,  k1,  k2, 0.0, -k1,  k2],
+            [ 0.0,  k2,  k3, 0.0, -k2,  k4],
+            [-axial, 0.0, 0.0,  axial, 0.0, 0.0],
+            [ 0.0, -k1, -k2, 0.0,  k1, -k2],
+            [ 0.0,  k2,  k4, 0.0, -k2,  k3],
+        ]
+    }
+
+    // Rotation from the frame axes to the element axes, the same for both ends
+    fn transform(direction: [f64; 2]) -> [[f64; 3]; 3] {
+        let [c, s] = direction;
+        [
+            [c, -s, 0.0],
+            [s,  c, 0.0],
+            [0.0, 0.0, 1.0],
+        ]
+    }
+
+    // Global DOF numbers of both ends
+    fn dofs(&self) -> [usize; 6] {
+        std::array::from_fn(|i| DOFS * self.nodes[i / DOFS] + i % DOFS)
+    }
+
+    // Stiffness in the frame axes, Tᵀ k T
+    fn global_stiffness(&self, nodes: &[Node]) -> [[f64; 6]; 6] {
+        let (length, direction) = self.length_and_direction(nodes);
+        let local = self.local_stiffness(length);
+        let t = Element::transform(direction);
+        let rotate = |i: usize, k: usize| if i / DOFS == k / DOFS { t[k % DOFS][i % DOFS] } else { 0.0 };
+        std::array::from_fn(|i| std::array::from_fn(|j| {
+            (0..6).map(|k| (0..6).map(|l| rotate(i, k) * local[k][l] * rotate(j, l)).sum::<f64>()).sum()
+        }))
+    }
+
+    // End actions from the displacements of the frame, k T d
+    fn end_forces(&self, nodes: &[Node], displacements: &[[f64; DOFS]]) -> ElementForces {
+        let (length, direction) = self.length_and_direction(nodes);
+        let local = self.local_stiffness(length);
+        let t = Element::transform(direction);
+        let global: Vec<f64> = self.nodes.iter().flat_map(|id| displacements[*id]).collect();
+        let local_displacements: [f64; 6] = std::array::from_fn(|i| {
+            let end = i / DOFS * DOFS;
+            (0..DOFS).map(|k| t[i % DOFS][k] * global[end + k]).sum()
+        });
+        let f: [f64; 6] = std::array::from_fn(|i| (0..6).map(|j| local[i][j] * local_displacements[j]).sum());
+        ElementForces { axial: [-f[0], f[3]], shear: [-f[1], -f[4]], moment: [f[2], f[5]] }
+    }
 }
 
 impl Frame {
-    fn transpose(matrix: [[f64; 4]; 4]) -> [[f64; 4]; 4] {
-        let mut transposed = [[0.0; 4]; 4];
-        for i in 0..4 {
-            for j in 0..4 {
-                transposed[i][j] = matrix[j][i];
-            }
-        }
-        transposed
+    pub fn new(nodes: Vec<Node>, elements: Vec<Element>) -> Self {
+        Frame { nodes, elements, loads: vec![] }
+    }
+
+    // Concentrated force and couple at a node: rightward, downward and clockwise positive
+    pub fn with_load(mut self, node: usize, load: [f64; 3]) -> Self {
+        self.loads.push((node, load));
+        self
+    }
+
+    pub fn get_nodes(&self) -> &[Node] {
+        &self.nodes
+    }
+
+    pub fn get_elements(&self) -> &[Element] {
+        &self.elements
     }
 
-    fn calculate_internal_forces(&self) -> Vec<(f64, f64)> {
-        let mut internal_forces = Vec::new();
+    // Stiffness matrix of the whole frame, assembled once from every element and spring support
+    fn assemble(&self) -> Vec<Vec<f64>> {
+        let size = DOFS * self.nodes.len();
+        let mut global_matrix = vec![vec![0.0; size]; size];
         for element in &self.elements {
-            // Calculate internal forces for each element
-            let node1 = &self.nodes[element.nodes[0]];
-            let node2 = &self.nodes[element.nodes[1]];
-            
-            let dx = node2.get_x() - node1.get_x();
-            let dy = node2.get_deflection() - node1.get_deflection();
-            let angle = dy.atan2(dx);
-            
-            let cos_angle = angle.cos();
-            let sin_angle = angle.sin();
-            
-            let k = element.material_properties.modulus_of_elasticity * element.material_properties.moment_of_inertia / element.length.powi(3);
-            let local_stiffness = [
-                [ k,         0.0, -k,         0.0],
-                [ 0.0,  3.0 * k,  0.0, -3.0 * k], 
-                [-k,         0.0,  k,         0.0],
-                [ 0.0, -3.0 * k,  0.0,  3.0 * k],
-            ];
-            
-            let c = cos_angle;
-            let s = sin_angle;
-            let transform = [
-                [ c,  s,  0.0,  0.0],
-                [-s,  c,  0.0,  0.0],
-                [0.0, 0.0,   c,    s],
-                [0.0, 0.0,  -s,    c],
-            ];
-            
-            let transposed_transform = Frame::transpose(transform);
-            let mut global_stiffness = [[0.0; 4]; 4];
-            for i in 0..4 {
-                for j in 0..4 {
-                    for k in 0..4 {
-                        global_stiffness[i][j] += transposed_transform[i][k] * local_stiffness[k][j];
-                    }
+            let stiffness = element.global_stiffness(&self.nodes);
+            let dofs = element.dofs();
+            for (i, row) in dofs.iter().enumerate() {
+                for (j, column) in dofs.iter().enumerate() {
+                    global_matrix[*row][*column] += stiffness[i][j];
                 }
             }
-            let mut final_global_stiffness = [[0.0; 4]; 4];
-            for i in 0..4 {
-                for j in 0..4 {
-                    for k in 0..4 {
-                        final_global_stiffness[i][j] += global_stiffness[i][k] * transform[k][j];
-                    }
-                }
-            }
-            
-            // Assemble global stiffness matrix
-            let mut global_matrix = vec![vec![0.0; 2*self.nodes.len()]; 2*self.nodes.len()];
-            for i in 0..4 {
-                for j in 0..4 {
-                    global_matrix[2*element.nodes[i/2]+i%2][2*element.nodes[j/2]+j%2] += global_stiffness[i][j];
-                }
-            }
-            
-            // Apply boundary conditions
-            for &node_id in &self.supports {
-                global_matrix[2*node_id][2*node_id] = 1.0;
-                global_matrix[2*node_id+1][2*node_id+1] = 1.0;
-                for j in 0..2*self.nodes.len() {
-                    if j != 2*node_id && j != 2*node_id+1 {
-                        global_matrix[2*node_id][j] = 0.0;
-                        global_matrix[2*node_id+1][j] = 0.0;
-                        global_matrix[j][2*node_id] = 0.0;
-                        global_matrix[j][2*node_id+1] = 0.0;
-                    }
+        }
+        for (id, node) in self.nodes.iter().enumerate() {
+            let [translational, rotational] = node.get_support().stiffness();
+            for (offset, stiffness) in [(1, translational), (2, rotational)] {
+                if stiffness.is_finite() {
+                    global_matrix[DOFS * id + offset][DOFS * id + offset] += stiffness;
                 }
             }
-            
-            // Solve for displacements
-            let mut rhs = vec![0.0; 2*self.nodes.len()];
-            for &(node_id, load) in &self.loads {
-                rhs[2*node_id] = load;
-            }
-            let displacements = gaussian_elimination(&global_matrix, &rhs);
-            
-            // Calculate internal forces from displacements
-            let mut local_displacements = [0.0; 4];
-            for i in 0..4 {
-                local_displacements[i] = displacements[2*element.nodes[i/2]+i%2];
-            }
-            let local_displacements = multiply_matrices(&local_stiffness, &local_displacements);
-            let local_forces = multiply_matrices(&transform, &local_displacements);
-            let shear_force = local_forces[0];
-            let bending_moment = local_forces[1];
-            
-            internal_forces.push((shear_force, bending_moment));
         }
-        internal_forces
+        global_matrix
     }
-}
 
-fn gaussian_elimination(matrix: &Vec<Vec<f64>>, rhs: &Vec<f64>) -> Vec<f64> {
-    // Placeholder for Gaussian elimination implementation
-    vec![0.0; rhs.len()]
-}
+    // Sway, deflection and rotation of every node. Held supports keep their settlement
+    pub fn displacements(&self) -> Result<Vec<[f64; DOFS]>, SolverError> {
+        let global_matrix = self.assemble();
+        let size = global_matrix.len();
+        let held: Vec<bool> = self.nodes.iter().flat_map(|node| restraints(node.get_support())).collect();
+        let mut displacements: Vec<f64> = self.nodes.iter().flat_map(|node| [0.0, node.get_deflection(), 0.0]).collect();
+        for (value, held) in displacements.iter_mut().zip(&held) {
+            if !held {
+                *value = 0.0;
+            }
+        }
 
-fn multiply_matrices(a: &[[f64; 4]; 4], b: &[f64; 4]) -> [f64; 4] {
-    let mut result = [0.0; 4];
-    for i in 0..4 {
-        for j in 0..4 {
-            result[i] += a[i][j] * b[j];
+        let mut loads = vec![0.0; size];
+        for (node_id, load) in &self.loads {
+            for (offset, value) in load.iter().enumerate() {
+                loads[DOFS * node_id + offset] += value;
+            }
         }
+
+        // Partition on the free DOFs, moving the settlements of the held ones to the right
+        let free: Vec<usize> = (0..size).filter(|dof| !held[*dof]).collect();
+        let matrix = free.iter().map(|&i| free.iter().map(|&j| global_matrix[i][j]).collect()).collect();
+        let rhs = free.iter().map(|&i| {
+            loads[i] - (0..size).filter(|j| held[*j]).map(|j| global_matrix[i][j] * displacements[j]).sum::<f64>()
+        }).collect();
+        let solution = cholesky::solve(matrix, rhs)?;
+        for (dof, value) in free.iter().zip(solution) {
+            displacements[*dof] = value;
+        }
+        Ok(displacements.chunks(DOFS).map(|chunk| [chunk[0], chunk[1], chunk[2]]).collect())
+    }
+
+    pub fn calculate_internal_forces(&self) -> Result<Vec<ElementForces>, SolverError> {
+        let displacements = self.displacements()?;
+        Ok(self.elements.iter().map(|element| element.end_forces(&self.nodes, &displacements)).collect())
     }
-    result
 }
 
 #[cfg(test)]
 mod frame_tests {
     use super::*;
-    use crate::nodes::node::{Node, SupportType, NodeTraits};
+    use crate::{
+        members::{frame::RigidFrame, traits::{FrameBuilder, FrameTraits}},
+        nodes::node::{Node, NodeTraits, SupportType},
+        spans::{span::Span, traits::SpanBuilder},
+        test_support::assert_close_to,
+    };
+
+    fn unit_section() -> MaterialProperties {
+        MaterialProperties { modulus_of_elasticity: 1.0, moment_of_inertia: 1.0, area: 1.0 }
+    }
 
     #[test]
     fn test_frame_analysis() {
         // Create nodes
         let node1 = Node::new(0.0, 0.0, SupportType::Fixed);
-        let node2 = Node::new(4.0, 0.0, SupportType::Hinged);
-        let node3 = Node::new(4.0, 3.0, SupportType::Hinged);
+        let node2 = Node::new(4.0, 0.0, SupportType::Free);
+        let node3 = Node::at(4.0, 3.0, SupportType::Free);
         let nodes = vec![node1, node2, node3];
         // Create elements
-        let element1 = Element {
-            nodes: [0, 1],
-            length: 4.0,
-            material_properties: MaterialProperties {
-                modulus_of_elasticity: 1.0,
-                moment_of_inertia: 1.0,
-            },
-        };
-        let element2 = Element {
-            nodes: [1, 2],
-            length: 3.0,
-            material_properties: MaterialProperties {
-                modulus_of_elasticity: 1.0,
-                moment_of_inertia: 1.0,
-            },
-        };
+        let element1 = Element { nodes: [0, 1], material_properties: unit_section() };
+        let element2 = Element { nodes: [1, 2], material_properties: unit_section() };
         let elements = vec![element1, element2];
 
-        // Create supports
-        let supports = vec![0];
-
-        // Create loads
-        let loads = vec![(2, 10.0)];
-        // Create frame
-        let frame = Frame {
-            nodes,
-            elements,
-            supports,
-            loads,
-            material_properties: MaterialProperties {
-                modulus_of_elasticity: 1.0,
-                moment_of_inertia: 1.0,
-            },
-        };
+        // Create frame with a rightward load at the top of the post
+        let frame = Frame::new(nodes, elements).with_load(2, [10.0, 0.0, 0.0]);
 
         // Analyze frame
-        let internal_forces = frame.calculate_internal_forces();
+        let internal_forces = frame.calculate_internal_forces().unwrap();
 
-        // Validate results
+        // Validate results: the post is a cantilever off the arm, which takes the load in
+        // tension and the moment Ph along its whole length
         assert_eq!(internal_forces.len(), 2);
-        assert!((internal_forces[0].0 - 7.5).abs() < 1e-6); // Shear force in element 1
-        assert!((internal_forces[0].1 - 15.0).abs() < 1e-6); // Bending moment in element 1
-        assert!((internal_forces[1].0 - 2.5).abs() < 1e-6); // Shear force in element 2
-        assert!((internal_forces[1].1 - 5.0).abs() < 1e-6); // Bending moment in element 2
+        let [arm, post] = [internal_forces[0], internal_forces[1]];
+        assert_close_to(10.0, arm.axial[0], 1e-9);
+        assert_close_to(10.0, arm.axial[1], 1e-9);
+        assert_close_to(0.0, arm.shear[0], 1e-9);
+        assert_close_to(-30.0, arm.moment[0], 1e-9);
+        assert_close_to(30.0, arm.moment[1], 1e-9);
+        assert_close_to(0.0, post.axial[0], 1e-9);
+        assert_close_to(10.0, post.shear[0], 1e-9);
+        assert_close_to(-10.0, post.shear[1], 1e-9);
+        assert_close_to(-30.0, post.moment[0], 1e-9);
+        assert_close_to(0.0, post.moment[1], 1e-9);
+    }
+
+    #[test]
+    fn test_column_shortens_under_axial_load() {
+        let base = Node::new(0.0, 0.0, SupportType::Fixed);
+        let top = Node::at(0.0, 3.0, SupportType::Free);
+        let section = MaterialProperties { modulus_of_elasticity: 200.0, moment_of_inertia: 1.0, area: 0.5 };
+        let frame = Frame::new(vec![base, top], vec![Element { nodes: [0, 1], material_properties: section }])
+            .with_load(1, [0.0, 30.0, 0.0]);
+
+        // PL/EA
+        let displacements = frame.displacements().unwrap();
+        assert_close_to(0.9, displacements[1][1], 1e-12);
+        assert_close_to(0.0, displacements[1][0], 1e-12);
+        let forces = frame.calculate_internal_forces().unwrap();
+        assert_close_to(-30.0, forces[0].axial[0], 1e-9);
+    }
+
+    #[test]
+    fn test_matches_slope_deflection_when_axially_rigid() {
+        // A guided base slides up and down but holds the sway like a fixed one
+        for base in [SupportType::Fixed, SupportType::Guided] {
+            let nodes = vec![
+                Node::new(0.0, 0.0, base),
+                Node::at(0.0, 4.0, SupportType::Free),
+                Node::at(6.0, 4.0, SupportType::Free),
+                Node::new(6.0, 0.0, SupportType::Fixed),
+            ];
+            let section = MaterialProperties { modulus_of_elasticity: 1000.0, moment_of_inertia: 1.0, area: 1e6 };
+            let connectivity = [[0, 1], [1, 2], [3, 2]];
+            let elements = connectivity.map(|nodes| Element { nodes, material_properties: section }).to_vec();
+            let frame = Frame::new(nodes.clone(), elements).with_load(1, [10.0, 0.0, 0.0]);
+            let forces = frame.calculate_internal_forces().unwrap();
+
+            let members = connectivity.map(|[a, b]| Span::new(nodes[a], nodes[b], vec![], vec![]).with_ei(1000.0)).to_vec();
+            let mut rigid_frame = RigidFrame::new(members).with_joint_load(1, 10.0, 0.0);
+            rigid_frame.analyze().unwrap();
+
+            for (forces, (moments, shears)) in forces.iter().zip(rigid_frame.get_end_moments().iter().zip(rigid_frame.get_end_shears())) {
+                for end in 0..2 {
+                    assert_close_to(moments[end], forces.moment[end], 1e-4);
+                    assert_close_to(shears[end], forces.shear[end], 1e-4);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_matches_slope_deflection_with_inclined_legs() {
+        // Legs splayed 1 m out over their 4 m height, so the knees sway and drop together
+        let nodes = vec![
+            Node::new(1.0, 0.0, SupportType::Fixed),
+            Node::at(0.0, 4.0, SupportType::Free),
+            Node::at(6.0, 4.0, SupportType::Free),
+            Node::new(7.0, 0.0, SupportType::Fixed),
+        ];
+        let section = MaterialProperties { modulus_of_elasticity: 1000.0, moment_of_inertia: 1.0, area: 1e6 };
+        let connectivity = [[0, 1], [1, 2], [3, 2]];
+        let elements = connectivity.map(|nodes| Element { nodes, material_properties: section }).to_vec();
+        let frame = Frame::new(nodes.clone(), elements).with_load(1, [10.0, 5.0, 0.0]);
+        let forces = frame.calculate_internal_forces().unwrap();
+        let displacements = frame.displacements().unwrap();
+
+        let members = connectivity.map(|[a, b]| Span::new(nodes[a], nodes[b], vec![], vec![]).with_ei(1000.0)).to_vec();
+        let mut rigid_frame = RigidFrame::new(members).with_joint_load(1, 10.0, 5.0);
+        rigid_frame.analyze().unwrap();
+
+        assert!(rigid_frame.get_end_moments().iter().flatten().any(|moment| moment.abs() > 1.0));
+        for (forces, (moments, shears)) in forces.iter().zip(rigid_frame.get_end_moments().iter().zip(rigid_frame.get_end_shears())) {
+            for end in 0..2 {
+                assert_close_to(moments[end], forces.moment[end], 1e-4);
+                assert_close_to(shears[end], forces.shear[end], 1e-4);
+            }
+        }
+        for (node, [sway, deflection, _]) in displacements.iter().enumerate() {
+            assert_close_to(*sway, rigid_frame.get_sways()[node], 1e-6);
+            assert_close_to(*deflection, rigid_frame.get_deflections()[node], 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_unsupported_frame_is_singular() {
+        let nodes = vec![Node::new(0.0, 0.0, SupportType::Free), Node::new(4.0, 0.0, SupportType::Roller)];
+        let frame = Frame::new(nodes, vec![Element { nodes: [0, 1], material_properties: unit_section() }]);
+        assert!(frame.calculate_internal_forces().is_err());
     }
 }
@@ -4,4 +4,6 @@ pub mod members;
 // pub mod edges;
 pub mod spans;
 pub mod frames;
-
+pub mod solvers;
+#[cfg(test)]
+mod test_support;
@@ -1,9 +1,6 @@
-use crate::{
-    nodes::node::SupportType,
-    spans::{span::Span, traits::SpanDetails},
-};
+use crate::spans::span::Span;
 
-use super::load_traits::LoadTraits;
+use super::{error::LoadError, load_traits::LoadTraits};
 
 #[derive(Debug, Default, Clone, Copy)]
 pub struct DistributedLoad {
@@ -11,69 +8,92 @@ pub struct DistributedLoad {
     pub end_value: f64,
     pub x0: f64,
     pub xf: f64,
-    pub tg: f64,
-    pub x_centroid: f64,
-    pub total_force: f64,
 }
 
 impl LoadTraits for DistributedLoad {
-    fn fem_a(&self, span: &Span) -> f64 {
-        let l = span.get_length();
-        let w = self.start_value - self.end_value;
-
-        match (span.get_est_a(), span.get_est_b()) {
-            (SupportType::Fixed, SupportType::Fixed) => {
-                if self.start_value == self.end_value {
-                    - (self.start_value * l * l) / 12.0
-                } else {
-                    - (w * l * l) / 20.0 + (self.end_value * l * l) / 12.0
-                }
-            }
-            (SupportType::Fixed, SupportType::Roller)
-            | (SupportType::Fixed, SupportType::Hinged)
-            | (SupportType::Roller, SupportType::Fixed)
-            | (SupportType::Roller, SupportType::Roller)
-            | (SupportType::Roller, SupportType::Hinged)
-            | (SupportType::Hinged, SupportType::Fixed)
-            | (SupportType::Hinged, SupportType::Roller)
-            | (SupportType::Hinged, SupportType::Hinged) => {
-                if self.start_value == self.end_value {
-                    - (self.start_value * l * l) / 8.0
-                } else {
-                    - (w * l * l) / 20.0 + (self.end_value * l * l) / 15.0
-                }
-            }
+    fn force_up_to(&self, _span: &Span, x: f64) -> f64 {
+        self.integral(x, 0)
+    }
+
+    fn moment_up_to(&self, _span: &Span, x: f64) -> f64 {
+        self.integral(x, 1)
+    }
+
+    fn slope_up_to(&self, _span: &Span, x: f64) -> f64 {
+        self.integral(x, 2)
+    }
+
+    fn deflection_up_to(&self, _span: &Span, x: f64) -> f64 {
+        self.integral(x, 3)
+    }
+}
+
+impl DistributedLoad {
+    // Linearly varying load from `start_value` at x0 to `end_value` at xf, measured from support A.
+    // Whether xf stays within the span is checked when the span is analysed
+    pub fn new(start_value: f64, end_value: f64, x0: f64, xf: f64) -> Result<Self, LoadError> {
+        if !(0.0 <= x0 && x0 < xf && xf.is_finite()) {
+            return Err(LoadError::InvalidRange(x0, xf));
         }
+        Ok(DistributedLoad { start_value, end_value, x0, xf })
+    }
+
+    pub fn uniform(value: f64, x0: f64, xf: f64) -> Result<Self, LoadError> {
+        DistributedLoad::new(value, value, x0, xf)
+    }
+
+    // Whether the load runs forward from support A and ends within a span of the given length
+    pub fn fits(&self, length: f64) -> bool {
+        0.0 <= self.x0 && self.x0 < self.xf && self.xf <= length + 1e-12
+    }
+
+    // Rate of change of the intensity along the load
+    pub fn get_slope(&self) -> f64 {
+        let length = self.xf - self.x0;
+        if length > 0.0 { (self.end_value - self.start_value) / length } else { 0.0 }
     }
 
-    fn fem_b(&self, span: &Span) -> f64 {
-        let l = span.get_length();
-        let w = self.start_value - self.end_value;
-
-        match (span.get_est_a(), span.get_est_b()) {
-            (SupportType::Fixed, SupportType::Fixed) => {
-                if self.start_value == self.end_value {
-                    (self.start_value * l * l) / 12.0
-                } else {
-                    (w * l * l) / 30.0 + (self.end_value * l * l) / 12.0
-                }
-            }
-            (SupportType::Fixed, SupportType::Roller)
-            | (SupportType::Fixed, SupportType::Hinged)
-            | (SupportType::Roller, SupportType::Fixed)
-            | (SupportType::Roller, SupportType::Roller)
-            | (SupportType::Roller, SupportType::Hinged)
-            | (SupportType::Hinged, SupportType::Fixed)
-            | (SupportType::Hinged, SupportType::Roller)
-            | (SupportType::Hinged, SupportType::Hinged) => 0.0,
+    // Resultant of the load
+    pub fn get_total_force(&self) -> f64 {
+        (self.start_value + self.end_value) * (self.xf - self.x0) / 2.0
+    }
+
+    // Position of the resultant, measured from support A
+    pub fn get_centroid(&self) -> f64 {
+        let length = self.xf - self.x0;
+        let sum = self.start_value + self.end_value;
+        if sum == 0.0 {
+            self.x0 + length / 2.0
+        } else {
+            self.x0 + length * (self.start_value + 2.0 * self.end_value) / (3.0 * sum)
+        }
+    }
+
+    // The same load with its intensity multiplied by a factor
+    pub fn scaled(&self, factor: f64) -> Self {
+        DistributedLoad { start_value: factor * self.start_value, end_value: factor * self.end_value, ..*self }
+    }
+
+    // Integral of w(s)(x - s)ⁿ/n! over the loaded part left of x
+    fn integral(&self, x: f64, order: i32) -> f64 {
+        let length = self.xf - self.x0;
+        if length <= 0.0 {
+            return 0.0;
         }
+        let d = x - self.x0;
+        let c = d.clamp(0.0, length);
+        let tg = (self.end_value - self.start_value) / length;
+        let power = |v: f64, k: i32| v.powi(k) / (1..=k).product::<i32>() as f64;
+
+        (self.start_value + tg * d) * (power(d, order + 1) - power(d - c, order + 1))
+            - tg * (order + 1) as f64 * (power(d, order + 2) - power(d - c, order + 2))
     }
 }
 
 #[cfg(test)]
 mod distributed_load_tests {
     use super::*;
-    use crate::{nodes::node::{Node, NodeTraits}, spans::traits::SpanBuilder};
+    use crate::{nodes::node::{Node, NodeTraits, SupportType}, spans::traits::SpanBuilder};
 
     #[test]
     fn test_distributed_load_with_fixed_supports() {
@@ -84,9 +104,6 @@ mod distributed_load_tests {
             end_value: 20.0,
             x0: 0.0,
             xf: 6.0,
-            tg: 0.0,
-            x_centroid: 3.0,
-            total_force: 120.0,
         }], vec![]);
 
         let distributed_load = DistributedLoad {
@@ -94,11 +111,148 @@ mod distributed_load_tests {
             end_value: 20.0,
             x0: 0.0,
             xf: 6.0,
-            tg: 0.0,
-            x_centroid: 3.0,
-            total_force: 120.0,
         };
         assert_eq!(distributed_load.fem_a(&span), -60.0); // Expected value for fem_a
         assert_eq!(distributed_load.fem_b(&span), 60.0); // Expected value for fem_b
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_triangular_fixed_fem() {
+        let start_node = Node::new(0.0, 0.0, SupportType::Roller);
+        let end_node = Node::new(6.0, 0.0, SupportType::Hinged);
+        let span = Span::new(start_node, end_node, vec![], vec![]);
+
+        let distributed_load = DistributedLoad::new(10.0, 0.0, 0.0, 6.0).unwrap();
+        assert_eq!(30.0, distributed_load.get_total_force());
+        assert_eq!(2.0, distributed_load.get_centroid());
+        assert!((distributed_load.fixed_fem_a(&span) + 18.0).abs() < 1e-9); // -wL²/20
+        assert!((distributed_load.fixed_fem_b(&span) - 12.0).abs() < 1e-9); // wL²/30
+        assert!((distributed_load.simple_reaction_a(&span) - 20.0).abs() < 1e-9);
+        assert!((distributed_load.simple_reaction_b(&span) - 10.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_integrals_of_triangular_load() {
+        let start_node = Node::new(0.0, 0.0, SupportType::Roller);
+        let end_node = Node::new(6.0, 0.0, SupportType::Hinged);
+        let span = Span::new(start_node, end_node, vec![], vec![]);
+
+        let distributed_load = DistributedLoad::new(0.0, 12.0, 0.0, 6.0).unwrap();
+        assert_eq!(2.0, distributed_load.get_slope());
+        // w(s) = 2s, so the integrals up to x are x², x³/3, x⁴/12 and x⁵/60
+        assert!((distributed_load.force_up_to(&span, 3.0) - 9.0).abs() < 1e-9);
+        assert!((distributed_load.moment_up_to(&span, 3.0) - 9.0).abs() < 1e-9);
+        assert!((distributed_load.slope_up_to(&span, 3.0) - 6.75).abs() < 1e-9);
+        assert!((distributed_load.deflection_up_to(&span, 3.0) - 4.05).abs() < 1e-9);
+        assert!((distributed_load.force_up_to(&span, 8.0) - 36.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_partial_uniform_load() {
+        let start_node = Node::new(0.0, 0.0, SupportType::Fixed);
+        let end_node = Node::new(8.0, 0.0, SupportType::Fixed);
+        let span = Span::new(start_node, end_node, vec![], vec![]);
+
+        // Left half loaded: 11wL²/192 and 5wL²/192
+        let distributed_load = DistributedLoad::uniform(12.0, 0.0, 4.0).unwrap();
+        assert!((distributed_load.fixed_fem_a(&span) + 44.0).abs() < 1e-9);
+        assert!((distributed_load.fixed_fem_b(&span) - 20.0).abs() < 1e-9);
+        assert!((distributed_load.simple_reaction_a(&span) - 36.0).abs() < 1e-9);
+        assert!((distributed_load.simple_reaction_b(&span) - 12.0).abs() < 1e-9);
+        assert_eq!(48.0, distributed_load.force_up_to(&span, 6.0));
+        assert_eq!(0.0, distributed_load.force_up_to(&span, -1.0));
+    }
+
+    #[test]
+    fn test_partial_trapezoidal_load() {
+        let start_node = Node::new(0.0, 0.0, SupportType::Fixed);
+        let end_node = Node::new(10.0, 0.0, SupportType::Fixed);
+        let span = Span::new(start_node, end_node, vec![], vec![]);
+
+        let distributed_load = DistributedLoad::new(6.0, 2.0, 2.0, 5.0).unwrap();
+        assert_eq!(12.0, distributed_load.get_total_force());
+        assert!((distributed_load.get_centroid() - 3.25).abs() < 1e-12);
+        // Beyond the load, its moment is the resultant times the lever arm to the centroid
+        let moment = distributed_load.moment_up_to(&span, 8.0);
+        assert!((moment - 12.0 * (8.0 - 3.25)).abs() < 1e-9);
+        // Closer to support A, the load gives the larger fixed end moment there
+        let fem = distributed_load.fixed_fem(&span);
+        let reactions = [distributed_load.simple_reaction_a(&span), distributed_load.simple_reaction_b(&span)];
+        assert!((reactions[0] + reactions[1] - 12.0).abs() < 1e-9);
+        assert!(fem[0] < 0.0 && fem[1] > 0.0 && fem[0].abs() > fem[1].abs());
+    }
+
+    #[test]
+    fn test_fem_for_every_support_combination() {
+        use SupportType::{Fixed, Hinged, Roller};
+        let distributed_load = DistributedLoad::new(0.0, 12.0, 0.0, 6.0).unwrap();
+
+        // Fixed ends give -wL²/30 and wL²/20, propped ends -7wL²/120 and wL²/15
+        let table = [
+            (Fixed, Fixed, -14.4, 21.6),
+            (Fixed, Hinged, -25.2, 0.0),
+            (Fixed, Roller, -25.2, 0.0),
+            (Hinged, Fixed, 0.0, 28.8),
+            (Roller, Fixed, 0.0, 28.8),
+            (Hinged, Hinged, 0.0, 0.0),
+            (Hinged, Roller, 0.0, 0.0),
+            (Roller, Hinged, 0.0, 0.0),
+            (Roller, Roller, 0.0, 0.0),
+        ];
+        for (est_a, est_b, fem_a, fem_b) in table {
+            let span = Span::new(Node::new(0.0, 0.0, est_a), Node::new(6.0, 0.0, est_b), vec![], vec![]);
+            assert!((distributed_load.fem_a(&span) - fem_a).abs() < 1e-9, "{:?}-{:?} at A", est_a, est_b);
+            assert!((distributed_load.fem_b(&span) - fem_b).abs() < 1e-9, "{:?}-{:?} at B", est_a, est_b);
+        }
+    }
+
+    #[test]
+    fn test_fem_next_to_guided_end() {
+        use SupportType::{Fixed, Guided, Hinged};
+        let distributed_load = DistributedLoad::uniform(10.0, 0.0, 6.0).unwrap();
+
+        // Guided cantilever: -wL²/3 at the built-in end and -wL²/6 at the sliding one
+        let table = [
+            (Fixed, Guided, -120.0, -60.0),
+            (Guided, Fixed, 60.0, 120.0),
+            (Hinged, Guided, 0.0, -180.0),
+            (Guided, Hinged, 180.0, 0.0),
+        ];
+        for (est_a, est_b, fem_a, fem_b) in table {
+            let span = Span::new(Node::new(0.0, 0.0, est_a), Node::new(6.0, 0.0, est_b), vec![], vec![]);
+            assert!((distributed_load.fem_a(&span) - fem_a).abs() < 1e-9, "{:?}-{:?} at A", est_a, est_b);
+            assert!((distributed_load.fem_b(&span) - fem_b).abs() < 1e-9, "{:?}-{:?} at B", est_a, est_b);
+        }
+    }
+
+    #[test]
+    fn test_fem_next_to_spring_end() {
+        use SupportType::{Fixed, Spring};
+        let distributed_load = DistributedLoad::uniform(10.0, 0.0, 6.0).unwrap();
+
+        // A spring counts as the rigid support it acts as while the joint is locked
+        let table = [
+            (Spring { translational: 1000.0, rotational: 500.0 }, -30.0, 30.0),
+            (Spring { translational: f64::INFINITY, rotational: 0.0 }, 0.0, 45.0),
+            (Spring { translational: 0.0, rotational: 500.0 }, 60.0, 120.0),
+            (Spring { translational: 0.0, rotational: 0.0 }, 0.0, 180.0),
+        ];
+        for (est_a, fem_a, fem_b) in table {
+            let span = Span::new(Node::new(0.0, 0.0, est_a), Node::new(6.0, 0.0, Fixed), vec![], vec![]);
+            assert!((distributed_load.fem_a(&span) - fem_a).abs() < 1e-9, "{:?} at A", est_a);
+            assert!((distributed_load.fem_b(&span) - fem_b).abs() < 1e-9, "{:?} at B", est_a);
+        }
+    }
+
+    #[test]
+    fn test_invalid_ranges_are_rejected() {
+        assert_eq!(Err(LoadError::InvalidRange(4.0, 2.0)), DistributedLoad::uniform(10.0, 4.0, 2.0).map(|load| load.x0));
+        assert!(DistributedLoad::uniform(10.0, -1.0, 2.0).is_err());
+        assert!(DistributedLoad::uniform(10.0, 2.0, 2.0).is_err());
+        assert!(DistributedLoad::uniform(10.0, 0.0, f64::INFINITY).is_err());
+
+        let load = DistributedLoad::uniform(10.0, 2.0, 5.0).unwrap();
+        assert!(load.fits(5.0));
+        assert!(!load.fits(4.0));
+    }
+}
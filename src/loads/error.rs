@@ -0,0 +1,17 @@
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LoadError {
+    // The load does not run forward from x0 to xf past support A, given as (x0, xf)
+    InvalidRange(f64, f64),
+}
+
+impl fmt::Display for LoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LoadError::InvalidRange(x0, xf) => write!(f, "the load range from {} to {} is not valid", x0, xf),
+        }
+    }
+}
+
+impl std::error::Error for LoadError {}
@@ -0,0 +1,131 @@
+use super::{distributed_load::DistributedLoad, moment_load::MomentLoad, point_load::PunctualLoad};
+
+// Origin of the loads in a case, which decides the factors a design code applies to it
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LoadCaseKind {
+    Dead,
+    Live,
+    Wind,
+    Settlement,
+    Other,
+}
+
+// Named group of loads applied together, spans and nodes given by their index in the member
+#[derive(Debug, Clone)]
+pub struct LoadCase {
+    name: String,
+    kind: LoadCaseKind,
+    distributed_loads: Vec<(usize, DistributedLoad)>,
+    punctual_loads: Vec<(usize, PunctualLoad)>,
+    moment_loads: Vec<(usize, MomentLoad)>,
+    joint_moments: Vec<(usize, f64)>,
+    settlements: Vec<(usize, f64)>,
+}
+
+impl LoadCase {
+    pub fn new(name: &str, kind: LoadCaseKind) -> Self {
+        LoadCase {
+            name: name.to_string(),
+            kind,
+            distributed_loads: vec![],
+            punctual_loads: vec![],
+            moment_loads: vec![],
+            joint_moments: vec![],
+            settlements: vec![],
+        }
+    }
+
+    pub fn with_distributed_load(mut self, span: usize, load: DistributedLoad) -> Self {
+        self.distributed_loads.push((span, load));
+        self
+    }
+
+    pub fn with_punctual_load(mut self, span: usize, load: PunctualLoad) -> Self {
+        self.punctual_loads.push((span, load));
+        self
+    }
+
+    pub fn with_moment_load(mut self, span: usize, load: MomentLoad) -> Self {
+        self.moment_loads.push((span, load));
+        self
+    }
+
+    // Concentrated couple applied directly at a node, clockwise positive
+    pub fn with_joint_moment(mut self, node: usize, moment: f64) -> Self {
+        self.joint_moments.push((node, moment));
+        self
+    }
+
+    // Imposed settlement of a supported node, downward positive
+    pub fn with_settlement(mut self, node: usize, settlement: f64) -> Self {
+        self.settlements.push((node, settlement));
+        self
+    }
+
+    // The same case with every load, couple and settlement multiplied by a factor
+    pub fn scaled(&self, factor: f64) -> Self {
+        LoadCase {
+            name: self.name.clone(),
+            kind: self.kind,
+            distributed_loads: self.distributed_loads.iter().map(|(span, load)| (*span, load.scaled(factor))).collect(),
+            punctual_loads: self.punctual_loads.iter().map(|(span, load)| {
+                (*span, PunctualLoad { value: factor * load.value, ..*load })
+            }).collect(),
+            moment_loads: self.moment_loads.iter().map(|(span, load)| {
+                (*span, MomentLoad { value: factor * load.value, ..*load })
+            }).collect(),
+            joint_moments: self.joint_moments.iter().map(|(node, moment)| (*node, factor * moment)).collect(),
+            settlements: self.settlements.iter().map(|(node, settlement)| (*node, factor * settlement)).collect(),
+        }
+    }
+
+    // The part of the case acting along one span
+    pub fn on_span(&self, span: usize, name: &str) -> Self {
+        LoadCase {
+            name: name.to_string(),
+            kind: self.kind,
+            distributed_loads: self.distributed_loads.iter().filter(|(i, _)| *i == span).copied().collect(),
+            punctual_loads: self.punctual_loads.iter().filter(|(i, _)| *i == span).copied().collect(),
+            moment_loads: self.moment_loads.iter().filter(|(i, _)| *i == span).copied().collect(),
+            joint_moments: vec![],
+            settlements: vec![],
+        }
+    }
+
+    // The part of the case acting directly at the nodes
+    pub fn at_joints(&self, name: &str) -> Self {
+        LoadCase {
+            joint_moments: self.joint_moments.clone(),
+            settlements: self.settlements.clone(),
+            ..LoadCase::new(name, self.kind)
+        }
+    }
+
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn get_kind(&self) -> LoadCaseKind {
+        self.kind
+    }
+
+    pub fn get_distributed_loads(&self) -> &[(usize, DistributedLoad)] {
+        &self.distributed_loads
+    }
+
+    pub fn get_punctual_loads(&self) -> &[(usize, PunctualLoad)] {
+        &self.punctual_loads
+    }
+
+    pub fn get_moment_loads(&self) -> &[(usize, MomentLoad)] {
+        &self.moment_loads
+    }
+
+    pub fn get_joint_moments(&self) -> &[(usize, f64)] {
+        &self.joint_moments
+    }
+
+    pub fn get_settlements(&self) -> &[(usize, f64)] {
+        &self.settlements
+    }
+}
@@ -0,0 +1,162 @@
+use super::load_case::{LoadCase, LoadCaseKind};
+
+// Factored sum of load cases, each term naming a case and its factor
+#[derive(Debug, Clone, PartialEq)]
+pub struct LoadCombination {
+    name: String,
+    factors: Vec<(String, f64)>,
+}
+
+impl LoadCombination {
+    pub fn new(name: &str) -> Self {
+        LoadCombination { name: name.to_string(), factors: vec![] }
+    }
+
+    pub fn with_factor(mut self, case: &str, factor: f64) -> Self {
+        self.factors.push((case.to_string(), factor));
+        self
+    }
+
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn get_factors(&self) -> &[(String, f64)] {
+        &self.factors
+    }
+}
+
+// Ultimate limit state combinations of a design code
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DesignCode {
+    // BS 8110-1 Table 2.1
+    Bs8110,
+    // EN 1990 expression 6.10 with the recommended ψ0 of 0.7 for imposed loads and 0.6 for wind
+    Eurocode0,
+    // ASCE 7 section 2.3 strength design
+    Asce7Lrfd,
+}
+
+impl DesignCode {
+    // Name and factors on dead, live and wind loads of every combination of the code
+    fn presets(&self) -> &'static [(&'static str, [f64; 3])] {
+        match self {
+            DesignCode::Bs8110 => &[
+                ("1.4G + 1.6Q", [1.4, 1.6, 0.0]),
+                ("1.0G + 1.6Q", [1.0, 1.6, 0.0]),
+                ("1.4G + 1.4W", [1.4, 0.0, 1.4]),
+                ("1.0G + 1.4W", [1.0, 0.0, 1.4]),
+                ("1.2G + 1.2Q + 1.2W", [1.2, 1.2, 1.2]),
+            ],
+            DesignCode::Eurocode0 => &[
+                ("1.35G + 1.5Q", [1.35, 1.5, 0.0]),
+                ("1.35G + 1.5Q + 0.9W", [1.35, 1.5, 0.9]),
+                ("1.35G + 1.05Q + 1.5W", [1.35, 1.05, 1.5]),
+                ("1.0G + 1.5W", [1.0, 0.0, 1.5]),
+            ],
+            DesignCode::Asce7Lrfd => &[
+                ("1.4D", [1.4, 0.0, 0.0]),
+                ("1.2D + 1.6L", [1.2, 1.6, 0.0]),
+                ("1.2D + 1.0L + 1.0W", [1.2, 1.0, 1.0]),
+                ("0.9D + 1.0W", [0.9, 0.0, 1.0]),
+            ],
+        }
+    }
+
+    // Factors on dead load over spans carrying live load and over those left unloaded, and on
+    // live load, for pattern loading
+    pub fn pattern_factors(&self) -> [f64; 3] {
+        match self {
+            DesignCode::Bs8110 => [1.4, 1.0, 1.6],
+            DesignCode::Eurocode0 => [1.35, 1.35, 1.5],
+            DesignCode::Asce7Lrfd => [1.2, 1.2, 1.6],
+        }
+    }
+
+    // Factor on imposed settlements, taken with the permanent actions
+    fn settlement_factor(&self) -> f64 {
+        match self {
+            DesignCode::Eurocode0 => 1.2,
+            DesignCode::Bs8110 | DesignCode::Asce7Lrfd => 1.0,
+        }
+    }
+
+    // Combinations of the code over the given cases. A combination calling for a kind of load
+    // none of the cases carries is left out, and cases of kind `Other` are left out of all
+    pub fn combinations(&self, cases: &[LoadCase]) -> Vec<LoadCombination> {
+        let has = |kind: LoadCaseKind| cases.iter().any(|case| case.get_kind() == kind);
+        let kinds = [LoadCaseKind::Dead, LoadCaseKind::Live, LoadCaseKind::Wind];
+        self.presets()
+            .iter()
+            .filter(|(_, factors)| kinds.iter().zip(factors).all(|(kind, factor)| *factor == 0.0 || has(*kind)))
+            .map(|(name, factors)| {
+                cases.iter().fold(LoadCombination::new(name), |combination, case| {
+                    let factor = match case.get_kind() {
+                        LoadCaseKind::Dead => factors[0],
+                        LoadCaseKind::Live => factors[1],
+                        LoadCaseKind::Wind => factors[2],
+                        LoadCaseKind::Settlement => self.settlement_factor(),
+                        LoadCaseKind::Other => 0.0,
+                    };
+                    if factor == 0.0 { combination } else { combination.with_factor(case.get_name(), factor) }
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod load_combination_tests {
+    use super::*;
+
+    #[test]
+    fn test_presets_follow_the_cases() {
+        let cases = [
+            LoadCase::new("Self weight", LoadCaseKind::Dead),
+            LoadCase::new("Finishes", LoadCaseKind::Dead),
+            LoadCase::new("Office", LoadCaseKind::Live),
+        ];
+
+        // Without a wind case only the gravity combinations remain
+        let combinations = DesignCode::Bs8110.combinations(&cases);
+        assert_eq!(2, combinations.len());
+        assert_eq!("1.4G + 1.6Q", combinations[0].get_name());
+        let factors = combinations[0].get_factors();
+        assert_eq!(("Self weight".to_string(), 1.4), factors[0]);
+        assert_eq!(("Finishes".to_string(), 1.4), factors[1]);
+        assert_eq!(("Office".to_string(), 1.6), factors[2]);
+
+        assert_eq!(1, DesignCode::Eurocode0.combinations(&cases).len());
+        assert_eq!(2, DesignCode::Asce7Lrfd.combinations(&cases).len());
+    }
+
+    #[test]
+    fn test_wind_and_settlement() {
+        let cases = [
+            LoadCase::new("G", LoadCaseKind::Dead),
+            LoadCase::new("W", LoadCaseKind::Wind),
+            LoadCase::new("S", LoadCaseKind::Settlement),
+            LoadCase::new("Test", LoadCaseKind::Other),
+        ];
+        let combinations = DesignCode::Eurocode0.combinations(&cases);
+        assert_eq!(vec!["1.0G + 1.5W"], combinations.iter().map(|c| c.get_name()).collect::<Vec<_>>());
+        assert_eq!(
+            &[("G".to_string(), 1.0), ("W".to_string(), 1.5), ("S".to_string(), 1.2)],
+            combinations[0].get_factors()
+        );
+    }
+
+    #[test]
+    fn test_accompanying_actions_take_their_psi0() {
+        let cases = [
+            LoadCase::new("G", LoadCaseKind::Dead),
+            LoadCase::new("Q", LoadCaseKind::Live),
+            LoadCase::new("W", LoadCaseKind::Wind),
+        ];
+        let combinations = DesignCode::Eurocode0.combinations(&cases);
+        assert_eq!(4, combinations.len());
+        assert_eq!("1.35G + 1.5Q + 0.9W", combinations[1].get_name());
+        assert_eq!(("W".to_string(), 0.9), combinations[1].get_factors()[2]);
+        assert_eq!(("Q".to_string(), 1.05), combinations[2].get_factors()[1]);
+    }
+}
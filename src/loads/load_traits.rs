@@ -1,9 +1,123 @@
-use crate::spans::span::Span;
+use crate::{nodes::node::SupportType, spans::{span::Span, traits::SpanDetails}};
+
+// Whether the support lets the member end rotate freely, carrying no moment at the end of a member
+fn is_pinned(support: SupportType) -> bool {
+    matches!(support, SupportType::Hinged | SupportType::Roller)
+}
+
+// Rigid support a spring acts as with the joint locked: a spring taking no moment releases the
+// rotation, one taking no force releases the translation, and any other spring holds the end
+fn locked(support: SupportType) -> SupportType {
+    match support {
+        SupportType::Spring { translational, rotational } => match (translational == 0.0, rotational == 0.0) {
+            (true, true) => SupportType::Free,
+            (false, true) => SupportType::Hinged,
+            (true, false) => SupportType::Guided,
+            (false, false) => SupportType::Fixed,
+        },
+        support => support,
+    }
+}
 
 pub trait LoadTraits {
-    fn fem_a(&self, span: &Span) -> f64;
-    fn fem_b(&self, span: &Span) -> f64;
+    // Fixed End Moment at support A, modified (propped) when support B is pinned and zero when A is.
+    // Next to a free end the moment is statically determined, that of a cantilever. A guided end
+    // takes no shear, so both end moments together balance the simple reaction there. A spring end
+    // counts as the support it acts as with the joint locked
+    fn fem_a(&self, span: &Span) -> f64 {
+        let [fixed_a, fixed_b] = self.fixed_fem(span);
+        let l = span.get_length();
+        match (locked(span.get_est_a()), locked(span.get_est_b())) {
+            (SupportType::Free, _) => 0.0,
+            (_, SupportType::Free) => -self.simple_reaction_b(span) * l,
+            (SupportType::Guided, SupportType::Guided) => 0.0,
+            (SupportType::Guided, est_b) if is_pinned(est_b) => self.simple_reaction_a(span) * l,
+            (SupportType::Guided, _) => (fixed_a - fixed_b + self.simple_reaction_a(span) * l) / 2.0,
+            (est_a, SupportType::Guided) if is_pinned(est_a) => 0.0,
+            (_, SupportType::Guided) => (fixed_a - fixed_b - self.simple_reaction_b(span) * l) / 2.0,
+            (est_a, est_b) => match (is_pinned(est_a), is_pinned(est_b)) {
+                (true, _) => 0.0,
+                (false, true) => fixed_a - fixed_b / 2.0,
+                (false, false) => fixed_a,
+            },
+        }
+    }
+
+    // Fixed End Moment at support B, modified (propped) when support A is pinned and zero when B is.
+    // Next to a free or guided end the moment follows as for `fem_a`
+    fn fem_b(&self, span: &Span) -> f64 {
+        let [fixed_a, fixed_b] = self.fixed_fem(span);
+        let l = span.get_length();
+        match (locked(span.get_est_a()), locked(span.get_est_b())) {
+            (_, SupportType::Free) => 0.0,
+            (SupportType::Free, _) => self.simple_reaction_a(span) * l,
+            (SupportType::Guided, SupportType::Guided) => 0.0,
+            (est_a, SupportType::Guided) if is_pinned(est_a) => -self.simple_reaction_b(span) * l,
+            (_, SupportType::Guided) => (fixed_b - fixed_a - self.simple_reaction_b(span) * l) / 2.0,
+            (SupportType::Guided, est_b) if is_pinned(est_b) => 0.0,
+            (SupportType::Guided, _) => (fixed_b - fixed_a + self.simple_reaction_a(span) * l) / 2.0,
+            (est_a, est_b) => match (is_pinned(est_a), is_pinned(est_b)) {
+                (_, true) => 0.0,
+                (true, false) => fixed_b - fixed_a / 2.0,
+                (false, false) => fixed_b,
+            },
+        }
+    }
+
     fn fem(&self, span: &Span) -> [f64; 2]{
         [self.fem_a(span), self.fem_b(span)]
     }
-}
\ No newline at end of file
+
+    // Fixed End Moment at support A with both ends fully restrained,
+    // from zero slope and deflection at support B of a cantilever built in at A
+    fn fixed_fem_a(&self, span: &Span) -> f64 {
+        let l = span.get_length();
+        (6.0 * self.deflection_up_to(span, l) - 2.0 * l * self.slope_up_to(span, l)) / l.powi(2)
+    }
+
+    // Fixed End Moment at support B with both ends fully restrained
+    fn fixed_fem_b(&self, span: &Span) -> f64 {
+        let l = span.get_length();
+        let fem_a = self.fixed_fem_a(span);
+        let shear_a = 2.0 * (self.slope_up_to(span, l) - fem_a * l) / l.powi(2);
+        self.moment_up_to(span, l) - fem_a - shear_a * l
+    }
+
+    fn fixed_fem(&self, span: &Span) -> [f64; 2] {
+        [self.fixed_fem_a(span), self.fixed_fem_b(span)]
+    }
+
+    // Upward reaction at support A of the load on a simply supported span
+    fn simple_reaction_a(&self, span: &Span) -> f64 {
+        let l = span.get_length();
+        self.moment_up_to(span, l) / l
+    }
+
+    // Upward reaction at support B of the load on a simply supported span
+    fn simple_reaction_b(&self, span: &Span) -> f64 {
+        let l = span.get_length();
+        self.force_up_to(span, l) - self.simple_reaction_a(span)
+    }
+
+    // Resultant of the load acting between support A and x, inclusive of x
+    fn force_up_to(&self, span: &Span, x: f64) -> f64;
+
+    // Resultant of the load acting between support A and x, exclusive of x
+    fn force_before(&self, span: &Span, x: f64) -> f64 {
+        self.force_up_to(span, x)
+    }
+
+    // Moment about x of the load acting between support A and x, clockwise positive
+    fn moment_up_to(&self, span: &Span, x: f64) -> f64;
+
+    // Moment about x of the load acting between support A and x, exclusive of x
+    fn moment_before(&self, span: &Span, x: f64) -> f64 {
+        self.moment_up_to(span, x)
+    }
+
+    // Integral of `moment_up_to` from support A to x, the load's share of EI times the slope
+    fn slope_up_to(&self, span: &Span, x: f64) -> f64;
+
+    // Integral of `slope_up_to` from support A to x, the load's share of EI times the deflection
+    fn deflection_up_to(&self, span: &Span, x: f64) -> f64;
+}
@@ -1,3 +1,7 @@
 pub mod point_load;
 pub mod distributed_load;
+pub mod moment_load;
+pub mod load_case;
+pub mod load_combination;
+pub mod error;
 pub mod load_traits;
\ No newline at end of file
@@ -0,0 +1,60 @@
+use crate::spans::span::Span;
+use super::load_traits::LoadTraits;
+
+// Concentrated couple applied at x, clockwise positive
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MomentLoad {
+    pub value: f64,
+    pub x: f64,
+}
+
+impl LoadTraits for MomentLoad {
+    fn force_up_to(&self, _span: &Span, _x: f64) -> f64 {
+        0.0
+    }
+
+    fn moment_up_to(&self, _span: &Span, x: f64) -> f64 {
+        if self.x <= x { -self.value } else { 0.0 }
+    }
+
+    fn moment_before(&self, _span: &Span, x: f64) -> f64 {
+        if self.x < x { -self.value } else { 0.0 }
+    }
+
+    fn slope_up_to(&self, _span: &Span, x: f64) -> f64 {
+        if self.x <= x { -self.value * (x - self.x) } else { 0.0 }
+    }
+
+    fn deflection_up_to(&self, _span: &Span, x: f64) -> f64 {
+        if self.x <= x { -self.value * (x - self.x).powi(2) / 2.0 } else { 0.0 }
+    }
+}
+
+#[cfg(test)]
+mod moment_load_tests {
+    use super::*;
+    use crate::{nodes::node::{Node, NodeTraits, SupportType}, spans::traits::{SpanBuilder, SpanTraits}};
+
+    #[test]
+    fn test_fixed_fem_of_couple() {
+        let span = Span::new(Node::new(0.0, 0.0, SupportType::Fixed), Node::new(6.0, 0.0, SupportType::Fixed), vec![], vec![]);
+        let load = MomentLoad { value: 36.0, x: 1.0 };
+
+        // Mb(2a - b)/L² and Ma(2b - a)/L²
+        assert!((load.fixed_fem_a(&span) + 15.0).abs() < 1e-9);
+        assert!((load.fixed_fem_b(&span) - 9.0).abs() < 1e-9);
+        assert!((load.simple_reaction_a(&span) + 6.0).abs() < 1e-9);
+        assert!((load.simple_reaction_b(&span) - 6.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_moment_jump_under_couple() {
+        let span = Span::new(Node::new(0.0, 0.0, SupportType::Hinged), Node::new(6.0, 0.0, SupportType::Roller), vec![], vec![])
+            .with_moment_loads(vec![MomentLoad { value: 36.0, x: 2.0 }]);
+
+        let points = span.diagram_at(&[2.0]);
+        assert_eq!(2, points.len());
+        assert!((points[1].moment - points[0].moment - 36.0).abs() < 1e-9);
+        assert_eq!(points[0].shear, points[1].shear);
+    }
+}
@@ -1,4 +1,4 @@
-use crate::{nodes::node::SupportType, spans::{span::Span, traits::SpanDetails}};
+use crate::spans::{span::Span, traits::SpanDetails};
 use super::load_traits::LoadTraits;
 
 #[derive(Debug, Clone, Copy, Default)]
@@ -8,38 +8,51 @@ pub struct PunctualLoad {
 }
 
 impl LoadTraits for PunctualLoad {
-    fn fem_a(&self, span: &Span) -> f64 {
+    fn fixed_fem_a(&self, span: &Span) -> f64 {
         let l = span.get_length();
-        let a = self.x;
-        let b = l - a;
-        let w = self.value;
-
-        match (span.get_est_a(), span.get_est_b()) {
-            (SupportType::Fixed, SupportType::Fixed) => -w * a * b.powi(2) / l.powi(2),
-            (SupportType::Fixed, SupportType::Roller) | (SupportType::Fixed, SupportType::Hinged) => {
-                -w / l.powi(2) * (b.powi(2) * a + a.powi(2) * b / 2.0)
-            }
-            _ => 0.0,
-        }
+        let b = l - self.x;
+        -self.value * self.x * b.powi(2) / l.powi(2)
     }
 
-    fn fem_b(&self, span: &Span) -> f64 {
+    fn fixed_fem_b(&self, span: &Span) -> f64 {
         let l = span.get_length();
         let b = l - self.x;
+        self.value * self.x.powi(2) * b / l.powi(2)
+    }
 
-        match (span.get_est_b(), span.get_est_a()) {
-            (SupportType::Fixed, SupportType::Fixed) => b * self.x.powi(2) * self.value / l.powi(2),
-            (SupportType::Fixed, SupportType::Roller) | (SupportType::Fixed, SupportType::Hinged) => {
-                self.value / l.powi(2) * (b.powi(2) * self.x / 2.0 + self.x.powi(2) * b)
-            }
-            _ => 0.0,
-        }
+    fn simple_reaction_a(&self, span: &Span) -> f64 {
+        let l = span.get_length();
+        self.value * (l - self.x) / l
+    }
+
+    fn simple_reaction_b(&self, span: &Span) -> f64 {
+        self.value * self.x / span.get_length()
+    }
+
+    fn force_up_to(&self, _span: &Span, x: f64) -> f64 {
+        if self.x <= x { self.value } else { 0.0 }
+    }
+
+    fn force_before(&self, _span: &Span, x: f64) -> f64 {
+        if self.x < x { self.value } else { 0.0 }
+    }
+
+    fn moment_up_to(&self, _span: &Span, x: f64) -> f64 {
+        if self.x <= x { self.value * (x - self.x) } else { 0.0 }
+    }
+
+    fn slope_up_to(&self, _span: &Span, x: f64) -> f64 {
+        if self.x <= x { self.value * (x - self.x).powi(2) / 2.0 } else { 0.0 }
+    }
+
+    fn deflection_up_to(&self, _span: &Span, x: f64) -> f64 {
+        if self.x <= x { self.value * (x - self.x).powi(3) / 6.0 } else { 0.0 }
     }
 }
 
 #[cfg(test)]
 mod punctual_load_test {
-    use crate::{nodes::node::{Node, NodeTraits}, spans::traits::SpanBuilder};
+    use crate::{nodes::node::{Node, NodeTraits, SupportType}, spans::traits::SpanBuilder};
 
     #[test]
     fn test_point_load_with_fixed_ends() {
@@ -92,4 +105,69 @@ mod punctual_load_test {
         assert_eq!(0.0, load.fem_a(&span));
         assert_eq!(90.0, load.fem_b(&span));
     }
+
+    #[test]
+    fn test_fixed_fem_ignores_support_types() {
+        use super::*;
+        let load = PunctualLoad { value: 40.0, x: 6.0 };
+
+        let start_node = Node::new(0.0, 0.0, SupportType::Hinged);
+        let end_node = Node::new(12.0, 0.0, SupportType::Roller);
+
+        let span = Span::new(start_node, end_node, vec![], vec![]);
+        assert_eq!([-60.0, 60.0], load.fixed_fem(&span));
+    }
+
+    #[test]
+    fn test_simple_reactions() {
+        use super::*;
+        let load = PunctualLoad { value: 40.0, x: 3.0 };
+
+        let start_node = Node::new(0.0, 0.0, SupportType::Hinged);
+        let end_node = Node::new(12.0, 0.0, SupportType::Roller);
+
+        let span = Span::new(start_node, end_node, vec![], vec![]);
+        assert_eq!(30.0, load.simple_reaction_a(&span));
+        assert_eq!(10.0, load.simple_reaction_b(&span));
+    }
+
+    #[test]
+    fn test_fem_for_every_support_combination() {
+        use super::*;
+        use SupportType::{Fixed, Hinged, Roller};
+        let load = PunctualLoad { value: 40.0, x: 3.0 };
+
+        // Fixed ends give -Pab²/L² and Pa²b/L², propped ends Pab(L + b)/2L² and Pab(L + a)/2L²
+        let table = [
+            (Fixed, Fixed, -67.5, 22.5),
+            (Fixed, Hinged, -78.75, 0.0),
+            (Fixed, Roller, -78.75, 0.0),
+            (Hinged, Fixed, 0.0, 56.25),
+            (Roller, Fixed, 0.0, 56.25),
+            (Hinged, Hinged, 0.0, 0.0),
+            (Hinged, Roller, 0.0, 0.0),
+            (Roller, Hinged, 0.0, 0.0),
+            (Roller, Roller, 0.0, 0.0),
+        ];
+        for (est_a, est_b, fem_a, fem_b) in table {
+            let span = Span::new(Node::new(0.0, 0.0, est_a), Node::new(12.0, 0.0, est_b), vec![], vec![]);
+            assert!((load.fem_a(&span) - fem_a).abs() < 1e-9, "{:?}-{:?} at A", est_a, est_b);
+            assert!((load.fem_b(&span) - fem_b).abs() < 1e-9, "{:?}-{:?} at B", est_a, est_b);
+            assert_eq!([-67.5, 22.5], load.fixed_fem(&span));
+        }
+    }
+
+    #[test]
+    fn test_fem_next_to_free_end() {
+        use super::*;
+        let load = PunctualLoad { value: 40.0, x: 3.0 };
+
+        let cantilever = Span::new(Node::new(0.0, 0.0, SupportType::Fixed), Node::new(12.0, 0.0, SupportType::Free), vec![], vec![]);
+        assert_eq!(-120.0, load.fem_a(&cantilever));
+        assert_eq!(0.0, load.fem_b(&cantilever));
+
+        let overhang = Span::new(Node::new(0.0, 0.0, SupportType::Free), Node::new(12.0, 0.0, SupportType::Roller), vec![], vec![]);
+        assert_eq!(0.0, load.fem_a(&overhang));
+        assert_eq!(360.0, load.fem_b(&overhang));
+    }
 }
\ No newline at end of file
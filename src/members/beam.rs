@@ -0,0 +1,1041 @@
+use crate::{
+    loads::load_case::LoadCase,
+    nodes::node::{DofState, Node, NodeTraits, SupportType},
+    solvers::cholesky::Cholesky,
+    spans::{diagram::{DeflectionPoint, DiagramPoint}, span::Span, traits::{SpanBuilder, SpanDetails, SpanTraits}},
+};
+use super::{
+    error::AnalysisError,
+    reaction::{EquilibriumCheck, Reaction},
+    traits::{BeamBuilder, BeamDetails, BeamTraits},
+};
+
+#[derive(Debug, Clone)]
+pub struct ContinuousBeam {
+    nodes: Vec<Node>,
+    spans: Vec<Span>,
+    joint_moments: Vec<f64>,
+    // Hinges asked for at stations that are neither a node nor inside a span
+    stray_hinges: Vec<f64>,
+    // Span of the beam as built that every span was cut from by a hinge
+    origins: Vec<usize>,
+    // Load cases left off the beam because they do not fit it
+    case_errors: Vec<AnalysisError>,
+}
+
+impl ContinuousBeam {
+    // Position of each node's translation, rotation and rotation right of a hinge in the unknowns
+    // vector, `None` where prescribed
+    fn dof_unknowns(&self) -> (Vec<[Option<usize>; 3]>, usize) {
+        let mut count = 0;
+        let mut number = |state: DofState| match state {
+            DofState::Prescribed => None,
+            DofState::Unknown => {
+                count += 1;
+                Some(count - 1)
+            }
+        };
+        let last = self.nodes.len().saturating_sub(1);
+        let unknowns = self.nodes.iter().enumerate().map(|(i, node)| {
+            let translation = number(node.get_translation_dof());
+            let rotation = number(node.get_rotation_dof());
+            // Across a hinge the member right of the node turns freely, even where the support
+            // holds the rotation of the member left of it
+            let rotation_right = if node.has_moment_release() && i != 0 && i != last {
+                number(DofState::Unknown)
+            } else {
+                rotation
+            };
+            [translation, rotation, rotation_right]
+        }).collect();
+        (unknowns, count)
+    }
+
+    fn check_connectivity(&self) -> Result<(), AnalysisError> {
+        if self.spans.is_empty() {
+            return Err(AnalysisError::NoSpans);
+        }
+        for (i, pair) in self.spans.windows(2).enumerate() {
+            if (pair[0].get_end_node().get_x() - pair[1].get_start_node().get_x()).abs() > 1e-9 {
+                return Err(AnalysisError::DisconnectedSpans(i));
+            }
+        }
+        Ok(())
+    }
+
+    // A hinge needs a member on either side of it
+    fn check_hinges(&self) -> Result<(), AnalysisError> {
+        if let Some(x) = self.stray_hinges.first() {
+            return Err(AnalysisError::InvalidHinge(*x));
+        }
+        let last = self.nodes.len() - 1;
+        match self.nodes.iter().enumerate().find(|(i, node)| node.has_moment_release() && (*i == 0 || *i == last)) {
+            Some((_, node)) => Err(AnalysisError::InvalidHinge(node.get_x())),
+            None => Ok(()),
+        }
+    }
+
+    fn check_loads(&self) -> Result<(), AnalysisError> {
+        if let Some(error) = self.case_errors.first() {
+            return Err(*error);
+        }
+        match self.spans.iter().position(|span| !span.loads_fit()) {
+            Some(i) => Err(AnalysisError::LoadOutsideSpan(i)),
+            None => Ok(()),
+        }
+    }
+
+    // Number of spans of the beam as built, before any hinge cut a span in two
+    pub(crate) fn built_spans(&self) -> usize {
+        self.origins.last().map_or(0, |last| last + 1)
+    }
+
+    // Node standing for node `k` of the beam as built
+    fn built_node(&self, k: usize) -> Option<usize> {
+        match k == self.built_spans() {
+            true => self.nodes.len().checked_sub(1),
+            false => self.origins.iter().position(|origin| *origin == k),
+        }
+    }
+
+    // Span of the beam as built holding a station of span `i`, and the station within it
+    pub(crate) fn built_station(&self, i: usize, x: f64) -> (usize, f64) {
+        let first = self.origins.iter().position(|origin| *origin == self.origins[i]).unwrap_or(i);
+        (self.origins[i], x + self.spans[i].get_start_node().get_x() - self.spans[first].get_start_node().get_x())
+    }
+
+    // Every span and node of a load case is on the beam as built, and every settlement at a node
+    // the supports hold vertically
+    pub(crate) fn check_load_case(&self, case: &LoadCase) -> Result<(), AnalysisError> {
+        let spans = case.get_distributed_loads().iter().map(|(i, _)| i)
+            .chain(case.get_punctual_loads().iter().map(|(i, _)| i))
+            .chain(case.get_moment_loads().iter().map(|(i, _)| i));
+        if let Some(i) = spans.into_iter().find(|i| **i >= self.built_spans()) {
+            return Err(AnalysisError::UnknownSpan(*i));
+        }
+        for (k, _) in case.get_joint_moments().iter().chain(case.get_settlements()) {
+            if self.built_node(*k).is_none() {
+                return Err(AnalysisError::UnknownNode(*k));
+            }
+        }
+        for (k, _) in case.get_settlements() {
+            if self.built_node(*k).is_some_and(|i| self.nodes[i].get_translation_dof() != DofState::Prescribed) {
+                return Err(AnalysisError::UnsupportedSettlement(*k));
+            }
+        }
+        Ok(())
+    }
+
+    // Copy the nodes of the beam into the end nodes of its spans, a span starting at a hinge
+    // takes the rotation right of it
+    fn sync_span_nodes(&mut self) {
+        for (i, span) in self.spans.iter_mut().enumerate() {
+            let mut start_node = self.nodes[i];
+            start_node.set_rotation(start_node.get_rotation_right());
+            span.set_nodes(start_node, self.nodes[i + 1]);
+        }
+    }
+
+    // Shear and moment rows of every span end, the span's own rows and the shear rows negated,
+    // the force the span exerts on the joint, to keep the matrix symmetric
+    fn span_rows(unknowns: &[[Option<usize>; 3]], i: usize) -> [(Option<usize>, f64); 4] {
+        let [near, far] = [unknowns[i], unknowns[i + 1]];
+        [(near[0], -1.0), (near[2], 1.0), (far[0], -1.0), (far[1], 1.0)]
+    }
+
+    // At every free joint the end moments balance the applied couple, and where the joint can
+    // translate the end shears balance each other. Across a hinge each side carries no moment on
+    // its own. The matrix depends on the geometry and supports only, not on the loads
+    fn stiffness_matrix(&self, unknowns: &[[Option<usize>; 3]], count: usize) -> Vec<Vec<f64>> {
+        let mut matrix = vec![vec![0.0; count]; count];
+        for ([translation, rotation, _], node) in unknowns.iter().zip(&self.nodes) {
+            // A spring support pushes back in proportion to the displacement
+            let [translational, rotational] = node.get_support().stiffness();
+            if let Some(row) = translation {
+                matrix[*row][*row] += translational;
+            }
+            if let Some(row) = rotation {
+                matrix[*row][*row] += rotational;
+            }
+        }
+        for (i, span) in self.spans.iter().enumerate() {
+            let stiffness = span.stiffness();
+            let rows = ContinuousBeam::span_rows(unknowns, i);
+            for (r, (row, sign)) in rows.into_iter().enumerate() {
+                let Some(row) = row else { continue };
+                for (c, (column, _)) in rows.iter().enumerate() {
+                    if let Some(column) = column {
+                        matrix[row][*column] += sign * stiffness[r][c];
+                    }
+                }
+            }
+        }
+        matrix
+    }
+
+    // Applied couples at the joints less the end actions of the spans with the unknown
+    // displacements cleared, which hold the fixed end actions, the chord rotation terms from
+    // settlements and any prescribed rotations. An applied couple at a hinge goes to its left side
+    fn load_vector(&self, unknowns: &[[Option<usize>; 3]], count: usize) -> Vec<f64> {
+        let mut rhs = vec![0.0; count];
+        for ([_, rotation, _], moment) in unknowns.iter().zip(&self.joint_moments) {
+            if let Some(row) = rotation {
+                rhs[*row] += moment;
+            }
+        }
+        for (i, span) in self.spans.iter().enumerate() {
+            let [shear_a, shear_b] = span.mes();
+            let [moment_a, moment_b] = span.mem();
+            let constants = [shear_a, moment_a, shear_b, moment_b];
+            for ((row, sign), constant) in ContinuousBeam::span_rows(unknowns, i).into_iter().zip(constants) {
+                if let Some(row) = row {
+                    rhs[row] -= sign * constant;
+                }
+            }
+        }
+        rhs
+    }
+
+    // Factorized stiffness matrix, shared by every load applied to the same beam
+    pub(crate) fn factorize(&self) -> Result<Cholesky, AnalysisError> {
+        self.check_connectivity()?;
+        self.check_hinges()?;
+        let (unknowns, count) = self.dof_unknowns();
+        Ok(Cholesky::factorize(self.stiffness_matrix(&unknowns, count))?)
+    }
+
+    // Solves the loads on the beam with the factors of its stiffness matrix
+    pub(crate) fn analyze_with(&mut self, factors: &Cholesky) -> Result<(), AnalysisError> {
+        self.check_loads()?;
+        let (unknowns, count) = self.dof_unknowns();
+        for (node, [translation, rotation, rotation_right]) in self.nodes.iter_mut().zip(&unknowns) {
+            if translation.is_some() {
+                node.set_deflection(0.0);
+            }
+            if rotation.is_some() {
+                node.set_rotation(0.0);
+            }
+            if rotation_right.is_some() {
+                node.set_rotation_right(0.0);
+            }
+        }
+        self.sync_span_nodes();
+
+        let solution = factors.solve(&self.load_vector(&unknowns, count))?;
+        for (node, [translation, rotation, rotation_right]) in self.nodes.iter_mut().zip(&unknowns) {
+            if let Some(index) = translation {
+                node.set_deflection(solution[*index]);
+            }
+            if let Some(index) = rotation {
+                node.set_rotation(solution[*index]);
+            }
+            if let Some(index) = rotation_right {
+                node.set_rotation_right(solution[*index]);
+            }
+        }
+        self.sync_span_nodes();
+        Ok(())
+    }
+
+    // The same beam with the loads, couples and settlements taken off
+    pub(crate) fn unloaded(&self) -> Self {
+        let mut beam = self.clone();
+        beam.spans = beam.spans.into_iter().map(|span| span.with_loads(vec![], vec![])).collect();
+        beam.joint_moments.iter_mut().for_each(|moment| *moment = 0.0);
+        for node in beam.nodes.iter_mut().filter(|node| node.get_translation_dof() == DofState::Prescribed) {
+            node.set_deflection(0.0);
+        }
+        beam.sync_span_nodes();
+        beam
+    }
+
+    // Sets every node to the factored sum of its displacements in analysed beams of the same geometry
+    pub(crate) fn superpose(&mut self, terms: &[(f64, &ContinuousBeam)]) {
+        for (i, node) in self.nodes.iter_mut().enumerate() {
+            let sum = |value: fn(&Node) -> f64| terms.iter().map(|(factor, beam)| factor * value(&beam.nodes[i])).sum::<f64>();
+            node.set_deflection(sum(Node::get_deflection));
+            node.set_rotation(sum(Node::get_rotation));
+            node.set_rotation_right(sum(Node::get_rotation_right));
+        }
+        self.sync_span_nodes();
+    }
+
+    // Span containing a station measured from the first node, supports belong to the span on their left
+    fn span_index_at(&self, x: f64) -> usize {
+        self.spans
+            .iter()
+            .position(|span| x <= span.get_end_node().get_x() + 1e-12)
+            .unwrap_or(self.spans.len().saturating_sub(1))
+    }
+}
+
+impl BeamBuilder for ContinuousBeam {
+    fn new(spans: Vec<Span>) -> Self {
+        let nodes: Vec<Node> = spans
+            .first()
+            .map(|span| span.get_start_node())
+            .into_iter()
+            .chain(spans.iter().map(|span| span.get_end_node()))
+            .collect();
+        let joint_moments = vec![0.0; nodes.len()];
+        let origins = (0..spans.len()).collect();
+        ContinuousBeam { nodes, spans, joint_moments, stray_hinges: vec![], origins, case_errors: vec![] }
+    }
+
+    fn with_joint_moment(mut self, node: usize, moment: f64) -> Self {
+        self.joint_moments[node] += moment;
+        self
+    }
+
+    fn with_settlement(mut self, node: usize, settlement: f64) -> Self {
+        self.nodes[node].set_deflection(settlement);
+        self.sync_span_nodes();
+        self
+    }
+
+    fn with_load_case(mut self, case: &LoadCase) -> Self {
+        if let Err(error) = self.check_load_case(case) {
+            self.case_errors.push(error);
+            return self;
+        }
+        for k in 0..self.built_spans() {
+            let distributed: Vec<_> = case.get_distributed_loads().iter().filter(|(index, _)| *index == k).map(|(_, load)| *load).collect();
+            let punctual: Vec<_> = case.get_punctual_loads().iter().filter(|(index, _)| *index == k).map(|(_, load)| *load).collect();
+            let moment: Vec<_> = case.get_moment_loads().iter().filter(|(index, _)| *index == k).map(|(_, load)| *load).collect();
+            let pieces: Vec<usize> = (0..self.spans.len()).filter(|i| self.origins[*i] == k).collect();
+            let [first, last] = [pieces[0], pieces[pieces.len() - 1]];
+
+            // The loads are laid on the span as built and shared out at the hinges cutting it
+            let whole = Span::new(self.spans[first].get_start_node(), self.spans[last].get_end_node(), distributed, punctual)
+                .with_moment_loads(moment);
+            let mut rest = whole;
+            for i in pieces {
+                let piece = match i == last {
+                    true => rest.clone(),
+                    false => {
+                        let [left, right] = rest.split_at(self.nodes[i + 1]);
+                        rest = right;
+                        left
+                    }
+                };
+                let span = &self.spans[i];
+                self.spans[i] = span.clone()
+                    .with_loads(
+                        span.get_distributed_loads().iter().chain(piece.get_distributed_loads()).copied().collect(),
+                        span.get_punctual_loads().iter().chain(piece.get_punctual_loads()).copied().collect(),
+                    )
+                    .with_moment_loads(span.get_moment_loads().iter().chain(piece.get_moment_loads()).copied().collect());
+            }
+        }
+        for (k, moment) in case.get_joint_moments() {
+            if let Some(node) = self.built_node(*k) {
+                self.joint_moments[node] += moment;
+            }
+        }
+        for (k, settlement) in case.get_settlements() {
+            if let Some(node) = self.built_node(*k) {
+                let settlement = self.nodes[node].get_deflection() + settlement;
+                self.nodes[node].set_deflection(settlement);
+            }
+        }
+        self.sync_span_nodes();
+        self
+    }
+
+    fn with_hinge_at(mut self, x: f64) -> Self {
+        if let Some(i) = self.nodes.iter().position(|node| (node.get_x() - x).abs() < 1e-9) {
+            self.nodes[i] = self.nodes[i].with_moment_release();
+        } else if let Some(i) = self.spans.iter().position(|span| {
+            span.get_start_node().get_x() < x && x < span.get_end_node().get_x()
+        }) {
+            let node = Node::new(x, 0.0, SupportType::Free).with_moment_release();
+            let [left, right] = self.spans[i].split_at(node);
+            self.spans.splice(i..=i, [left, right]);
+            self.origins.insert(i, self.origins[i]);
+            self.nodes.insert(i + 1, node);
+            self.joint_moments.insert(i + 1, 0.0);
+        } else {
+            self.stray_hinges.push(x);
+        }
+        self.sync_span_nodes();
+        self
+    }
+}
+
+impl BeamDetails for ContinuousBeam {
+    fn get_nodes(&self) -> &[Node] {
+        &self.nodes
+    }
+
+    fn get_spans(&self) -> &[Span] {
+        &self.spans
+    }
+
+    fn get_joint_moments(&self) -> &[f64] {
+        &self.joint_moments
+    }
+}
+
+impl BeamTraits for ContinuousBeam {
+    fn analyze(&mut self) -> Result<(), AnalysisError> {
+        let factors = self.factorize()?;
+        self.analyze_with(&factors)
+    }
+
+    fn get_rotations(&self) -> Vec<f64> {
+        self.nodes.iter().map(|node| node.get_rotation()).collect()
+    }
+
+    fn get_deflections(&self) -> Vec<f64> {
+        self.nodes.iter().map(|node| node.get_deflection()).collect()
+    }
+
+    fn get_end_moments(&self) -> Vec<[f64; 2]> {
+        self.spans.iter().map(|span| span.mem()).collect()
+    }
+
+    fn get_end_shears(&self) -> Vec<[f64; 2]> {
+        self.spans.iter().map(|span| span.mes()).collect()
+    }
+
+    fn get_reactions(&self) -> Vec<Reaction> {
+        let mut reactions: Vec<Reaction> = self.nodes.iter().enumerate().map(|(i, node)| Reaction {
+            node: i,
+            x: node.get_x(),
+            force: 0.0,
+            moment: 0.0,
+        }).collect();
+
+        for (i, span) in self.spans.iter().enumerate() {
+            let [shear_a, shear_b] = span.mes();
+            let [moment_a, moment_b] = span.mem();
+            reactions[i].force += shear_a;
+            reactions[i].moment += moment_a;
+            reactions[i + 1].force += shear_b;
+            reactions[i + 1].moment += moment_b;
+        }
+
+        for ((reaction, node), moment) in reactions.iter_mut().zip(&self.nodes).zip(&self.joint_moments) {
+            // Where the support lets the node move, the reaction is whatever its spring takes
+            let [translational, rotational] = node.get_support().stiffness();
+            if node.get_translation_dof() == DofState::Unknown {
+                reaction.force = translational * node.get_deflection();
+            }
+            reaction.moment = match node.get_rotation_dof() {
+                DofState::Prescribed => reaction.moment - moment,
+                DofState::Unknown => -rotational * node.get_rotation(),
+            };
+        }
+        reactions
+    }
+
+    fn check_equilibrium(&self) -> EquilibriumCheck {
+        let origin = self.nodes.first().map_or(0.0, |node| node.get_x());
+        let (applied_force, applied_moment) = self.spans.iter().fold((0.0, 0.0), |(force, moment), span| {
+            let [reaction_a, reaction_b] = span.simple_reactions();
+            let offset = span.get_start_node().get_x() - origin;
+            (
+                force + reaction_a + reaction_b,
+                moment + (reaction_a + reaction_b) * offset + reaction_b * span.get_length(),
+            )
+        });
+
+        let applied_moment = applied_moment + self.joint_moments.iter().sum::<f64>();
+
+        let reactions = self.get_reactions();
+        let reaction_force = reactions.iter().fold(0.0, |accum, reaction| accum + reaction.force);
+        let reaction_moment = reactions.iter().fold(0.0, |accum, reaction| {
+            accum - reaction.force * (reaction.x - origin) + reaction.moment
+        });
+
+        EquilibriumCheck {
+            applied_force,
+            reaction_force,
+            force_residual: applied_force - reaction_force,
+            moment_residual: applied_moment + reaction_moment,
+        }
+    }
+
+    fn diagram_at(&self, stations: &[f64]) -> Vec<DiagramPoint> {
+        stations.iter().flat_map(|&x| {
+            let span = &self.spans[self.span_index_at(x)];
+            let offset = span.get_start_node().get_x();
+            span.diagram_at(&[x - offset])
+                .into_iter()
+                .map(move |point| DiagramPoint { x: point.x + offset, ..point })
+        }).collect()
+    }
+
+    fn get_diagram(&self, points: usize) -> Vec<DiagramPoint> {
+        self.spans.iter().flat_map(|span| {
+            let offset = span.get_start_node().get_x();
+            span.diagram(points)
+                .into_iter()
+                .map(move |point| DiagramPoint { x: point.x + offset, ..point })
+        }).collect()
+    }
+
+    fn get_deflection_curve(&self, points: usize) -> Vec<DeflectionPoint> {
+        self.spans.iter().flat_map(|span| {
+            let offset = span.get_start_node().get_x();
+            span.deflection_curve(points)
+                .into_iter()
+                .map(move |point| DeflectionPoint { x: point.x + offset, ..point })
+        }).collect()
+    }
+
+    fn get_max_deflections(&self) -> Vec<DeflectionPoint> {
+        self.spans.iter().map(|span| {
+            let point = span.max_deflection();
+            DeflectionPoint { x: point.x + span.get_start_node().get_x(), ..point }
+        }).collect()
+    }
+}
+
+#[cfg(test)]
+mod continuous_beam_tests {
+    use super::*;
+    use crate::{
+        loads::{distributed_load::DistributedLoad, moment_load::MomentLoad, point_load::PunctualLoad},
+        test_support::{assert_close, assert_close_to},
+    };
+
+    fn udl(value: f64, length: f64) -> DistributedLoad {
+        DistributedLoad::uniform(value, 0.0, length).unwrap()
+    }
+
+    #[test]
+    fn test_propped_cantilever_with_point_load() {
+        let a = Node::new(0.0, 0.0, SupportType::Fixed);
+        let b = Node::new(4.0, 0.0, SupportType::Hinged);
+        let span = Span::new(a, b, vec![], vec![PunctualLoad { value: 40.0, x: 2.0 }]);
+
+        let mut beam = ContinuousBeam::new(vec![span]);
+        beam.analyze().unwrap();
+
+        let moments = beam.get_end_moments();
+        assert_close(-30.0, moments[0][0]); // -3PL/16
+        assert_close(0.0, moments[0][1]);
+    }
+
+    #[test]
+    fn test_two_equal_spans_with_udl() {
+        let a = Node::new(0.0, 0.0, SupportType::Hinged);
+        let b = Node::new(6.0, 0.0, SupportType::Roller);
+        let c = Node::new(12.0, 0.0, SupportType::Roller);
+        let ab = Span::new(a, b, vec![udl(10.0, 6.0)], vec![]);
+        let bc = Span::new(b, c, vec![udl(10.0, 6.0)], vec![]);
+
+        let mut beam = ContinuousBeam::new(vec![ab, bc]);
+        beam.analyze().unwrap();
+
+        let moments = beam.get_end_moments();
+        assert_close(0.0, moments[0][0]);
+        assert_close(45.0, moments[0][1]); // wL²/8 over the middle support
+        assert_close(-45.0, moments[1][0]);
+        assert_close(0.0, moments[1][1]);
+        assert_close(0.0, beam.get_rotations()[1]);
+    }
+
+    #[test]
+    fn test_fixed_beam_with_unequal_spans() {
+        let a = Node::new(0.0, 0.0, SupportType::Fixed);
+        let b = Node::new(4.0, 0.0, SupportType::Roller);
+        let c = Node::new(10.0, 0.0, SupportType::Fixed);
+        let ab = Span::new(a, b, vec![udl(12.0, 4.0)], vec![]);
+        let bc = Span::new(b, c, vec![], vec![PunctualLoad { value: 30.0, x: 3.0 }]);
+
+        let mut beam = ContinuousBeam::new(vec![ab, bc]);
+        beam.analyze().unwrap();
+
+        // 2(1/4 + 1/6)·2θB = -(16 - 22.5)
+        let theta_b = 6.5 / (4.0 * (1.0 / 4.0 + 1.0 / 6.0));
+        assert_close(theta_b, beam.get_rotations()[1]);
+
+        let moments = beam.get_end_moments();
+        assert_close(0.0, moments[0][1] + moments[1][0]);
+        assert_close(-16.0 + theta_b / 2.0, moments[0][0]);
+        assert_close(22.5 + theta_b / 3.0, moments[1][1]);
+
+        let solved = beam.get_spans()[0].get_end_node();
+        assert_close(theta_b, solved.get_rotation());
+        assert_close(0.0, beam.get_nodes()[0].get_rotation());
+    }
+
+    #[test]
+    fn test_spans_with_different_stiffness() {
+        let a = Node::new(0.0, 0.0, SupportType::Fixed);
+        let b = Node::new(6.0, 0.0, SupportType::Roller);
+        let c = Node::new(12.0, 0.0, SupportType::Fixed);
+        let ab = Span::new(a, b, vec![udl(10.0, 6.0)], vec![]);
+        let bc = Span::new(b, c, vec![], vec![]).with_ei(2.0);
+
+        let mut beam = ContinuousBeam::new(vec![ab, bc]);
+        beam.analyze().unwrap();
+
+        // (4EI/L + 4(2EI)/L)θB = -wL²/12
+        let theta_b = -30.0 / (4.0 / 6.0 + 8.0 / 6.0);
+        assert_close(theta_b, beam.get_rotations()[1]);
+
+        let moments = beam.get_end_moments();
+        assert_close(30.0 + 4.0 * theta_b / 6.0, moments[0][1]);
+        assert_close(8.0 * theta_b / 6.0, moments[1][0]);
+        assert_close(2.0 * 2.0 * theta_b / 6.0, moments[1][1]);
+        assert!(beam.check_equilibrium().is_satisfied(1e-9));
+    }
+
+    #[test]
+    fn test_reactions_of_two_equal_spans() {
+        let a = Node::new(0.0, 0.0, SupportType::Hinged);
+        let b = Node::new(6.0, 0.0, SupportType::Roller);
+        let c = Node::new(12.0, 0.0, SupportType::Roller);
+        let ab = Span::new(a, b, vec![udl(10.0, 6.0)], vec![]);
+        let bc = Span::new(b, c, vec![udl(10.0, 6.0)], vec![]);
+
+        let mut beam = ContinuousBeam::new(vec![ab, bc]);
+        beam.analyze().unwrap();
+
+        // 3wL/8, 10wL/8, 3wL/8
+        let reactions = beam.get_reactions();
+        assert_close(22.5, reactions[0].force);
+        assert_close(75.0, reactions[1].force);
+        assert_close(22.5, reactions[2].force);
+        assert!(reactions.iter().all(|reaction| reaction.moment == 0.0));
+        assert!(beam.check_equilibrium().is_satisfied(1e-9));
+    }
+
+    #[test]
+    fn test_reactions_of_fixed_supports() {
+        let a = Node::new(0.0, 0.0, SupportType::Fixed);
+        let b = Node::new(4.0, 0.0, SupportType::Roller);
+        let c = Node::new(10.0, 0.0, SupportType::Fixed);
+        let ab = Span::new(a, b, vec![udl(12.0, 4.0)], vec![]);
+        let bc = Span::new(b, c, vec![], vec![PunctualLoad { value: 30.0, x: 3.0 }]);
+
+        let mut beam = ContinuousBeam::new(vec![ab, bc]);
+        beam.analyze().unwrap();
+
+        let moments = beam.get_end_moments();
+        let reactions = beam.get_reactions();
+        assert_close(moments[0][0], reactions[0].moment);
+        assert_close(0.0, reactions[1].moment);
+        assert_close(moments[1][1], reactions[2].moment);
+
+        let check = beam.check_equilibrium();
+        assert_close(78.0, check.applied_force);
+        assert_close(78.0, check.reaction_force);
+        assert!(check.is_satisfied(1e-9));
+    }
+
+    #[test]
+    fn test_diagram_of_two_equal_spans() {
+        let a = Node::new(0.0, 0.0, SupportType::Hinged);
+        let b = Node::new(6.0, 0.0, SupportType::Roller);
+        let c = Node::new(12.0, 0.0, SupportType::Roller);
+        let ab = Span::new(a, b, vec![udl(10.0, 6.0)], vec![]);
+        let bc = Span::new(b, c, vec![udl(10.0, 6.0)], vec![]);
+
+        let mut beam = ContinuousBeam::new(vec![ab, bc]);
+        beam.analyze().unwrap();
+
+        let diagram = beam.get_diagram(3);
+        assert_eq!(6, diagram.len());
+        assert_close(-45.0, diagram[2].moment);
+        assert_close(-37.5, diagram[2].shear);
+        assert_close(37.5, diagram[3].shear);
+
+        // Maximum sagging moment 9wL²/128 at 3L/8
+        let points = beam.diagram_at(&[2.25, 9.75]);
+        assert_close(25.3125, points[0].moment);
+        assert_close(25.3125, points[1].moment);
+        assert_close(0.0, points[1].shear);
+        assert_close(9.75, points[1].x);
+    }
+
+    #[test]
+    fn test_deflection_of_two_equal_spans() {
+        let a = Node::new(0.0, 0.0, SupportType::Hinged);
+        let b = Node::new(6.0, 0.0, SupportType::Roller);
+        let c = Node::new(12.0, 0.0, SupportType::Roller);
+        let ab = Span::new(a, b, vec![udl(10.0, 6.0)], vec![]);
+        let bc = Span::new(b, c, vec![udl(10.0, 6.0)], vec![]);
+
+        let mut beam = ContinuousBeam::new(vec![ab, bc]);
+        beam.analyze().unwrap();
+
+        let curve = beam.get_deflection_curve(5);
+        assert!(curve.iter().all(|point| point.deflection >= -1e-9));
+        assert_close(0.0, curve[4].deflection);
+        assert_close(0.0, curve[4].slope);
+
+        // wL⁴/185EI at 0.4215L from the end supports
+        let peaks = beam.get_max_deflections();
+        let x = 6.0 * (1.0 + 33f64.sqrt()) / 16.0;
+        assert_close_to(x, peaks[0].x, 1e-6);
+        assert_close_to(12.0 - x, peaks[1].x, 1e-6);
+        assert_close(beam.get_spans()[0].deflection_at(x), peaks[0].deflection);
+        assert_close_to(1.0, peaks[0].deflection / (10.0 * 6f64.powi(4) / 185.0), 0.01);
+    }
+
+    #[test]
+    fn test_settlement_of_middle_support() {
+        let a = Node::new(0.0, 0.0, SupportType::Hinged);
+        let b = Node::new(6.0, 0.01, SupportType::Roller);
+        let c = Node::new(12.0, 0.0, SupportType::Roller);
+        let ab = Span::new(a, b, vec![], vec![]).with_ei(36000.0);
+        let bc = Span::new(b, c, vec![], vec![]).with_ei(36000.0);
+
+        let mut beam = ContinuousBeam::new(vec![ab, bc]);
+        beam.analyze().unwrap();
+
+        // B acts as a fixed end by symmetry, M = 3EIΔ/L²
+        let moments = beam.get_end_moments();
+        assert_close(-30.0, moments[0][1]);
+        assert_close(30.0, moments[1][0]);
+        assert_close(0.0, beam.get_rotations()[1]);
+        assert_close(0.0025, beam.get_rotations()[0]);
+
+        let reactions = beam.get_reactions();
+        assert_close(5.0, reactions[0].force);
+        assert_close(-10.0, reactions[1].force);
+        assert!(beam.check_equilibrium().is_satisfied(1e-9));
+
+        let curve = beam.get_deflection_curve(3);
+        assert_close(0.01, curve[2].deflection);
+        assert_close(0.01, curve[3].deflection);
+    }
+
+    #[test]
+    fn test_repeated_analysis() {
+        let a = Node::new(0.0, 0.0, SupportType::Fixed);
+        let b = Node::new(4.0, 0.0, SupportType::Roller);
+        let ab = Span::new(a, b, vec![udl(12.0, 4.0)], vec![]);
+
+        let mut beam = ContinuousBeam::new(vec![ab]);
+        beam.analyze().unwrap();
+        let first = beam.get_end_moments();
+        beam.analyze().unwrap();
+        assert_eq!(first, beam.get_end_moments());
+        assert_close(-24.0, first[0][0]); // -wL²/8
+    }
+
+    #[test]
+    fn test_partial_load_on_continuous_beam() {
+        let a = Node::new(0.0, 0.0, SupportType::Fixed);
+        let b = Node::new(8.0, 0.0, SupportType::Roller);
+        let c = Node::new(14.0, 0.0, SupportType::Roller);
+        let ab = Span::new(a, b, vec![DistributedLoad::uniform(12.0, 0.0, 4.0).unwrap()], vec![]);
+        let bc = Span::new(b, c, vec![DistributedLoad::new(0.0, 9.0, 2.0, 6.0).unwrap()], vec![]);
+
+        let mut beam = ContinuousBeam::new(vec![ab, bc]);
+        beam.analyze().unwrap();
+
+        let moments = beam.get_end_moments();
+        assert_close(0.0, moments[0][1] + moments[1][0]);
+        assert_close(0.0, moments[1][1]);
+
+        let check = beam.check_equilibrium();
+        assert_close(66.0, check.applied_force);
+        assert!(check.is_satisfied(1e-9));
+
+        // Unloaded stretch between 4 and 8, the shear is constant
+        let points = beam.diagram_at(&[5.0, 7.0, 9.0]);
+        assert_close(points[0].shear, points[1].shear);
+        assert_close(moments[1][0], points[2].moment - points[2].shear * 1.0);
+    }
+
+    #[test]
+    fn test_couples_along_span_and_at_joint() {
+        let a = Node::new(0.0, 0.0, SupportType::Fixed);
+        let b = Node::new(6.0, 0.0, SupportType::Roller);
+        let c = Node::new(10.0, 0.0, SupportType::Hinged);
+        let ab = Span::new(a, b, vec![], vec![]).with_moment_loads(vec![MomentLoad { value: 30.0, x: 2.0 }]);
+        let bc = Span::new(b, c, vec![], vec![]);
+
+        let mut beam = ContinuousBeam::new(vec![ab, bc]).with_joint_moment(1, -20.0);
+        beam.analyze().unwrap();
+
+        let moments = beam.get_end_moments();
+        assert_close(-20.0, moments[0][1] + moments[1][0]);
+        assert_close(0.0, moments[1][1]);
+        assert!(beam.check_equilibrium().is_satisfied(1e-9));
+
+        // The moment diagram jumps by the couple under it
+        let points = beam.diagram_at(&[2.0]);
+        assert_close(30.0, points[1].moment - points[0].moment);
+    }
+
+    #[test]
+    fn test_joint_moment_on_fixed_support() {
+        let a = Node::new(0.0, 0.0, SupportType::Fixed);
+        let b = Node::new(5.0, 0.0, SupportType::Roller);
+
+        let mut beam = ContinuousBeam::new(vec![Span::new(a, b, vec![], vec![])])
+            .with_joint_moment(0, 10.0)
+            .with_joint_moment(1, 10.0);
+        beam.analyze().unwrap();
+
+        // Half the couple at the roller carries over to the fixed end
+        let moments = beam.get_end_moments();
+        assert_close(10.0, moments[0][1]);
+        assert_close(5.0, moments[0][0]);
+        assert_close(-5.0, beam.get_reactions()[0].moment);
+        assert!(beam.check_equilibrium().is_satisfied(1e-9));
+    }
+
+    #[test]
+    fn test_cantilever_tip_deflection() {
+        let a = Node::new(0.0, 0.0, SupportType::Fixed);
+        let b = Node::new(3.0, 0.0, SupportType::Free);
+        let span = Span::new(a, b, vec![], vec![PunctualLoad { value: 10.0, x: 3.0 }]).with_ei(900.0);
+
+        let mut beam = ContinuousBeam::new(vec![span]);
+        beam.analyze().unwrap();
+
+        // PL³/3EI and PL²/2EI at the tip
+        assert_close(0.1, beam.get_deflections()[1]);
+        assert_close(0.05, beam.get_rotations()[1]);
+        assert_close(0.1, beam.get_spans()[0].deflection_at(3.0));
+
+        let moments = beam.get_end_moments();
+        assert_close(-30.0, moments[0][0]);
+        assert_close(0.0, moments[0][1]);
+
+        let reactions = beam.get_reactions();
+        assert_close(10.0, reactions[0].force);
+        assert_close(-30.0, reactions[0].moment);
+        assert_eq!(0.0, reactions[1].force);
+        assert!(beam.check_equilibrium().is_satisfied(1e-9));
+    }
+
+    #[test]
+    fn test_cantilever_propped_by_spring() {
+        let a = Node::new(0.0, 0.0, SupportType::Fixed);
+        let b = Node::new(3.0, 0.0, SupportType::Spring { translational: 100.0, rotational: 0.0 });
+        let span = Span::new(a, b, vec![], vec![PunctualLoad { value: 10.0, x: 3.0 }]).with_ei(900.0);
+
+        let mut beam = ContinuousBeam::new(vec![span]);
+        beam.analyze().unwrap();
+
+        // The spring and the cantilever, 3EI/L³ = 100, share the load equally
+        assert_close(0.05, beam.get_deflections()[1]);
+        let reactions = beam.get_reactions();
+        assert_close(5.0, reactions[0].force);
+        assert_close(-15.0, reactions[0].moment);
+        assert_close(5.0, reactions[1].force);
+        assert_close(0.0, reactions[1].moment);
+        assert!(beam.check_equilibrium().is_satisfied(1e-9));
+    }
+
+    #[test]
+    fn test_rotational_spring_support() {
+        let a = Node::new(0.0, 0.0, SupportType::Spring { translational: f64::INFINITY, rotational: 1000.0 });
+        let b = Node::new(6.0, 0.0, SupportType::Fixed);
+        let span = Span::new(a, b, vec![udl(10.0, 6.0)], vec![]).with_ei(1500.0);
+
+        let mut beam = ContinuousBeam::new(vec![span]);
+        beam.analyze().unwrap();
+
+        // A spring as stiff as the member itself, 4EI/L, takes half the fixed end moment
+        assert_close(0.015, beam.get_rotations()[0]);
+        let moments = beam.get_end_moments();
+        assert_close(-15.0, moments[0][0]);
+        assert_close(37.5, moments[0][1]);
+
+        let reactions = beam.get_reactions();
+        assert_close(-15.0, reactions[0].moment);
+        assert_close(37.5, reactions[1].moment);
+        assert!(beam.check_equilibrium().is_satisfied(1e-9));
+    }
+
+    #[test]
+    fn test_symmetric_half_on_guided_support() {
+        let a = Node::new(0.0, 0.0, SupportType::Hinged);
+        let b = Node::new(6.0, 0.0, SupportType::Guided);
+        let span = Span::new(a, b, vec![udl(10.0, 6.0)], vec![]).with_ei(1000.0);
+
+        let mut beam = ContinuousBeam::new(vec![span]);
+        beam.analyze().unwrap();
+
+        // Half of a 12 m simply supported span: wL²/8 and 5wL⁴/384EI at midspan
+        let moments = beam.get_end_moments();
+        assert_close(0.0, moments[0][0]);
+        assert_close(-180.0, moments[0][1]);
+        assert_close(5.0 * 10.0 * 12f64.powi(4) / 384.0 / 1000.0, beam.get_deflections()[1]);
+        assert_close(0.0, beam.get_rotations()[1]);
+
+        let reactions = beam.get_reactions();
+        assert_close(60.0, reactions[0].force);
+        assert_close(0.0, reactions[1].force);
+        assert_close(-180.0, reactions[1].moment);
+        assert!(beam.check_equilibrium().is_satisfied(1e-9));
+    }
+
+    #[test]
+    fn test_overhanging_beam() {
+        let a = Node::new(0.0, 0.0, SupportType::Hinged);
+        let b = Node::new(6.0, 0.0, SupportType::Roller);
+        let c = Node::new(8.0, 0.0, SupportType::Free);
+        let ab = Span::new(a, b, vec![udl(10.0, 6.0)], vec![]);
+        let bc = Span::new(b, c, vec![], vec![PunctualLoad { value: 15.0, x: 2.0 }]);
+
+        let mut beam = ContinuousBeam::new(vec![ab, bc]);
+        beam.analyze().unwrap();
+
+        // The overhang moment -Pa is statically determined and carried into the joint at B
+        let moments = beam.get_end_moments();
+        assert_close(30.0, moments[0][1]);
+        assert_close(-30.0, moments[1][0]);
+        assert_close(0.0, moments[1][1]);
+
+        let reactions = beam.get_reactions();
+        assert_close(25.0, reactions[0].force);
+        assert_close(50.0, reactions[1].force);
+        assert!(beam.check_equilibrium().is_satisfied(1e-9));
+
+        // Tip deflection from the rotation at B plus the cantilever bending, Pa³/3EI
+        let theta_b = beam.get_rotations()[1];
+        assert_close(theta_b * 2.0 + 15.0 * 8.0 / 3.0, beam.get_deflections()[2]);
+    }
+
+    #[test]
+    fn test_gerber_beam_with_hinge_in_span() {
+        let a = Node::new(0.0, 0.0, SupportType::Fixed);
+        let b = Node::new(10.0, 0.0, SupportType::Roller);
+        let ab = Span::new(a, b, vec![udl(10.0, 10.0)], vec![]);
+
+        let mut beam = ContinuousBeam::new(vec![ab]).with_hinge_at(4.0);
+        assert_eq!(3, beam.get_nodes().len());
+        beam.analyze().unwrap();
+
+        // The drop-in span is simply supported and hangs its reaction off the cantilever tip
+        let moments = beam.get_end_moments();
+        assert_close(-200.0, moments[0][0]);
+        assert_close(0.0, moments[0][1]);
+        assert_close(0.0, moments[1][0]);
+        assert_close(0.0, moments[1][1]);
+
+        let reactions = beam.get_reactions();
+        assert_close(70.0, reactions[0].force);
+        assert_close(30.0, reactions[2].force);
+        assert!(beam.check_equilibrium().is_satisfied(1e-9));
+
+        // The two sides of the hinge share its deflection but not its rotation
+        let hinge = beam.get_nodes()[1];
+        let [left, right] = [&beam.get_spans()[0], &beam.get_spans()[1]];
+        assert_close(hinge.get_deflection(), left.deflection_at(4.0));
+        assert_close(hinge.get_rotation(), left.slope_at(4.0));
+        assert_close(hinge.get_rotation_right(), right.slope_at(0.0));
+        assert!((hinge.get_rotation() - hinge.get_rotation_right()).abs() > 1e-6);
+    }
+
+    #[test]
+    fn test_hinge_over_support() {
+        let a = Node::new(0.0, 0.0, SupportType::Fixed);
+        let b = Node::new(6.0, 0.0, SupportType::Roller);
+        let c = Node::new(12.0, 0.0, SupportType::Fixed);
+        let ab = Span::new(a, b, vec![udl(10.0, 6.0)], vec![]);
+        let bc = Span::new(b, c, vec![], vec![]);
+
+        let mut beam = ContinuousBeam::new(vec![ab, bc]).with_hinge_at(6.0);
+        beam.analyze().unwrap();
+
+        // Each side becomes a propped cantilever, only the loaded one bends
+        let moments = beam.get_end_moments();
+        assert_close(-45.0, moments[0][0]);
+        assert_close(0.0, moments[0][1]);
+        assert_close(0.0, moments[1][0]);
+        assert_close(0.0, moments[1][1]);
+        assert_close(0.0, beam.get_nodes()[1].get_rotation_right());
+    }
+
+    #[test]
+    fn test_unstable_beam() {
+        let a = Node::new(0.0, 0.0, SupportType::Hinged);
+        let b = Node::new(6.0, 0.0, SupportType::Free);
+
+        let mut beam = ContinuousBeam::new(vec![Span::new(a, b, vec![], vec![])]);
+        assert_eq!(Err(AnalysisError::Unstable), beam.analyze());
+    }
+
+    #[test]
+    fn test_stiff_spring_next_to_a_slender_span() {
+        // A rotational spring a trillion times stiffer than the span acts as a fixed end
+        let a = Node::new(0.0, 0.0, SupportType::Spring { translational: f64::INFINITY, rotational: 1e12 });
+        let b = Node::new(6.0, 0.0, SupportType::Roller);
+        let ab = Span::new(a, b, vec![DistributedLoad::uniform(10.0, 0.0, 6.0).unwrap()], vec![]).with_ei(1.0);
+
+        let mut beam = ContinuousBeam::new(vec![ab]);
+        beam.analyze().unwrap();
+        assert_close_to(-45.0, beam.get_end_moments()[0][0], 1e-6);
+        assert_close_to(0.0, beam.get_end_moments()[0][1], 1e-6);
+    }
+
+    #[test]
+    fn test_solver_errors_other_than_a_mechanism_are_passed_on() {
+        use crate::solvers::error::SolverError;
+        assert_eq!(AnalysisError::Unstable, AnalysisError::from(SolverError::NotPositiveDefinite(1)));
+        assert_eq!(AnalysisError::Unstable, AnalysisError::from(SolverError::Singular(0)));
+        let error = SolverError::IllConditioned(2);
+        assert_eq!(AnalysisError::Solver(error), AnalysisError::from(error));
+        assert_eq!(AnalysisError::Solver(SolverError::DimensionMismatch), AnalysisError::from(SolverError::DimensionMismatch));
+    }
+
+    #[test]
+    fn test_disconnected_spans() {
+        let a = Node::new(0.0, 0.0, SupportType::Fixed);
+        let b = Node::new(4.0, 0.0, SupportType::Roller);
+        let c = Node::new(5.0, 0.0, SupportType::Roller);
+        let d = Node::new(8.0, 0.0, SupportType::Fixed);
+
+        let mut beam = ContinuousBeam::new(vec![Span::new(a, b, vec![], vec![]), Span::new(c, d, vec![], vec![])]);
+        assert_eq!(Err(AnalysisError::DisconnectedSpans(0)), beam.analyze());
+        assert_eq!(Err(AnalysisError::NoSpans), ContinuousBeam::new(vec![]).analyze());
+    }
+
+    #[test]
+    fn test_load_beyond_span() {
+        let a = Node::new(0.0, 0.0, SupportType::Fixed);
+        let b = Node::new(4.0, 0.0, SupportType::Roller);
+        let c = Node::new(8.0, 0.0, SupportType::Fixed);
+
+        let bc = Span::new(b, c, vec![DistributedLoad::uniform(10.0, 2.0, 6.0).unwrap()], vec![]);
+        let mut beam = ContinuousBeam::new(vec![Span::new(a, b, vec![], vec![]), bc]);
+        assert_eq!(Err(AnalysisError::LoadOutsideSpan(1)), beam.analyze());
+    }
+
+    #[test]
+    fn test_hinge_beside_fixed_support() {
+        let a = Node::new(0.0, 0.0, SupportType::Fixed);
+        let b = Node::new(6.0, 0.0, SupportType::Fixed);
+        let c = Node::new(12.0, 0.0, SupportType::Fixed);
+        let ab = Span::new(a, b, vec![udl(15.0, 6.0)], vec![]);
+        let bc = Span::new(b, c, vec![udl(15.0, 6.0)], vec![]);
+
+        let mut beam = ContinuousBeam::new(vec![ab, bc]).with_hinge_at(6.0);
+        beam.analyze().unwrap();
+
+        // The left span stays built in at B, the right one is propped there: wL²/8 at C
+        let moments = beam.get_end_moments();
+        assert_close(-45.0, moments[0][0]);
+        assert_close(45.0, moments[0][1]);
+        assert_close(0.0, moments[1][0]);
+        assert_close(67.5, moments[1][1]);
+        assert_close(0.0, beam.get_nodes()[1].get_rotation());
+        // wL³/48EI clockwise at the propped end
+        assert_close(15.0 * 216.0 / 48.0, beam.get_nodes()[1].get_rotation_right());
+    }
+
+    #[test]
+    fn test_hinge_without_member_on_both_sides() {
+        let a = Node::new(0.0, 0.0, SupportType::Fixed);
+        let b = Node::new(6.0, 0.0, SupportType::Roller);
+        let c = Node::new(12.0, 0.0, SupportType::Fixed);
+        let beam = || ContinuousBeam::new(vec![Span::new(a, b, vec![], vec![]), Span::new(b, c, vec![], vec![])]);
+
+        assert_eq!(Err(AnalysisError::InvalidHinge(0.0)), beam().with_hinge_at(0.0).analyze());
+        assert_eq!(Err(AnalysisError::InvalidHinge(12.0)), beam().with_hinge_at(12.0).analyze());
+        assert_eq!(Err(AnalysisError::InvalidHinge(20.0)), beam().with_hinge_at(20.0).analyze());
+        assert_eq!(Err(AnalysisError::InvalidHinge(-1.0)), beam().with_hinge_at(-1.0).analyze());
+    }
+}
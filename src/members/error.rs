@@ -0,0 +1,68 @@
+use std::fmt;
+
+use crate::solvers::error::SolverError;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AnalysisError {
+    // The member has no spans to analyse
+    NoSpans,
+    // The end of span `i` does not coincide with the start of span `i + 1`
+    DisconnectedSpans(usize),
+    // The equilibrium equations are singular, the structure is a mechanism
+    Unstable,
+    // Node `i` sways, is hinged or otherwise falls outside what the method handles
+    UnsupportedNode(usize),
+    // The iteration was stopped after this many cycles without converging
+    NotConverged(usize),
+    // Combination `i` calls for a load case the model does not have
+    UnknownLoadCase(usize),
+    // A load on span `i` runs backwards or beyond the ends of the span
+    LoadOutsideSpan(usize),
+    // The hinge at this station has no member on one side of it
+    InvalidHinge(f64),
+    // The settlements of the supports would stretch or shorten a member
+    IncompatibleSettlement,
+    // The beam has no node `i`
+    UnknownNode(usize),
+    // The beam has no span `i`
+    UnknownSpan(usize),
+    // Node `i` is not held vertically, its deflection is solved and cannot be imposed
+    UnsupportedSettlement(usize),
+    // The section at this station lies off the beam
+    SectionOutsideBeam(f64),
+    // The solver rejected the equations for a reason other than a mechanism
+    Solver(SolverError),
+}
+
+impl fmt::Display for AnalysisError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AnalysisError::NoSpans => write!(f, "the member has no spans"),
+            AnalysisError::DisconnectedSpans(i) => write!(f, "span {} is not connected to span {}", i, i + 1),
+            AnalysisError::Unstable => write!(f, "the structure is unstable"),
+            AnalysisError::UnsupportedNode(i) => write!(f, "node {} is not supported by the method", i),
+            AnalysisError::NotConverged(cycles) => write!(f, "no convergence after {} cycles", cycles),
+            AnalysisError::UnknownLoadCase(i) => write!(f, "combination {} refers to an unknown load case", i),
+            AnalysisError::LoadOutsideSpan(i) => write!(f, "a load on span {} lies outside the span", i),
+            AnalysisError::InvalidHinge(x) => write!(f, "the hinge at {} has no member on one side", x),
+            AnalysisError::IncompatibleSettlement => write!(f, "the settlements would change the length of a member"),
+            AnalysisError::UnknownNode(i) => write!(f, "the beam has no node {}", i),
+            AnalysisError::UnknownSpan(i) => write!(f, "the beam has no span {}", i),
+            AnalysisError::UnsupportedSettlement(i) => write!(f, "node {} is not held vertically and cannot settle", i),
+            AnalysisError::SectionOutsideBeam(x) => write!(f, "the section at {} lies outside the beam", x),
+            AnalysisError::Solver(error) => write!(f, "the solver failed: {}", error),
+        }
+    }
+}
+
+impl std::error::Error for AnalysisError {}
+
+// A stiffness matrix without a usable pivot belongs to a mechanism, any other failure is passed on
+impl From<SolverError> for AnalysisError {
+    fn from(error: SolverError) -> Self {
+        match error {
+            SolverError::Singular(_) | SolverError::NotPositiveDefinite(_) => AnalysisError::Unstable,
+            _ => AnalysisError::Solver(error),
+        }
+    }
+}
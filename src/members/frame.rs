@@ -0,0 +1,438 @@
+use crate::{
+    nodes::node::{DofState, Node, NodeTraits, SupportType},
+    solvers::cholesky,
+    spans::{span::Span, traits::{SpanBuilder, SpanDetails, SpanTraits}},
+};
+use super::{
+    error::AnalysisError,
+    traits::{FrameBuilder, FrameDetails, FrameTraits},
+};
+
+// Plane frame of beams and columns meeting at rigid joints. Every member is a span measured from
+// its start node, with loads acting square to it on the clockwise side of its direction: downward
+// on a beam drawn left to right, rightward on a column drawn bottom to top. Members do not change
+// length, so the joints translate only in the sway mechanisms the members allow: a storey of
+// vertical columns sways as one, and inclined members turn about their instantaneous centres
+#[derive(Debug, Clone)]
+pub struct RigidFrame {
+    nodes: Vec<Node>,
+    members: Vec<Span>,
+    connectivity: Vec<[usize; 2]>,
+    joint_moments: Vec<f64>,
+    joint_loads: Vec<[f64; 2]>,
+    sways: Vec<f64>,
+}
+
+// Sway and deflection of every joint
+type Translations = Vec<[f64; 2]>;
+
+// Whether the support stops the joint moving sideways, a guided support sliding only vertically
+fn holds_sway(support: SupportType) -> bool {
+    matches!(support, SupportType::Fixed | SupportType::Hinged | SupportType::Guided)
+}
+
+impl RigidFrame {
+    // Joint translations the members allow without changing length, as the sway and deflection
+    // of every joint: a particular one taking up the settlements of the supports, and a basis of
+    // the sway mechanisms, the null space of the members' compatibility equations
+    fn mechanisms(&self) -> Result<(Translations, Vec<Translations>), AnalysisError> {
+        let mut translations = vec![[0.0; 2]; self.nodes.len()];
+        let mut columns = vec![[None; 2]; self.nodes.len()];
+        let mut free: Vec<(usize, usize)> = vec![];
+        for (i, node) in self.nodes.iter().enumerate() {
+            if !holds_sway(node.get_support()) {
+                columns[i][0] = Some(free.len());
+                free.push((i, 0));
+            }
+            if node.get_translation_dof() == DofState::Prescribed {
+                translations[i][1] = node.get_deflection();
+            } else {
+                columns[i][1] = Some(free.len());
+                free.push((i, 1));
+            }
+        }
+
+        // The ends of every member move alike along it, the deflection acting against its sine
+        let mut rows: Vec<(Vec<f64>, f64)> = self.members.iter().zip(&self.connectivity).map(|(member, [a, b])| {
+            let [c, s] = member.get_direction();
+            let mut row = vec![0.0; free.len()];
+            let mut rhs = 0.0;
+            for (node, sign) in [(*a, -1.0), (*b, 1.0)] {
+                for (axis, share) in [(0, c), (1, -s)] {
+                    match columns[node][axis] {
+                        Some(column) => row[column] += sign * share,
+                        None => rhs -= sign * share * translations[node][axis],
+                    }
+                }
+            }
+            (row, rhs)
+        }).collect();
+
+        // Reduced row echelon form, every pivot column solved in terms of the free ones
+        let mut pivots: Vec<usize> = vec![];
+        for column in 0..free.len() {
+            let r = pivots.len();
+            let Some(best) = (r..rows.len()).max_by(|i, j| rows[*i].0[column].abs().total_cmp(&rows[*j].0[column].abs())) else {
+                break;
+            };
+            if rows[best].0[column].abs() < 1e-9 {
+                continue;
+            }
+            rows.swap(r, best);
+            let pivot = rows[r].0[column];
+            rows[r].0.iter_mut().for_each(|value| *value /= pivot);
+            rows[r].1 /= pivot;
+            let (pivot_row, pivot_rhs) = rows[r].clone();
+            for (i, (row, rhs)) in rows.iter_mut().enumerate() {
+                let factor = row[column];
+                if i != r && factor != 0.0 {
+                    row.iter_mut().zip(&pivot_row).for_each(|(value, pivot)| *value -= factor * pivot);
+                    *rhs -= factor * pivot_rhs;
+                }
+            }
+            pivots.push(column);
+        }
+        if rows[pivots.len()..].iter().any(|(_, rhs)| rhs.abs() > 1e-12) {
+            return Err(AnalysisError::IncompatibleSettlement);
+        }
+
+        for (r, column) in pivots.iter().enumerate() {
+            let (node, axis) = free[*column];
+            translations[node][axis] = rows[r].1;
+        }
+        let modes = (0..free.len()).filter(|column| !pivots.contains(column)).map(|column| {
+            let mut mode = vec![[0.0; 2]; self.nodes.len()];
+            let (node, axis) = free[column];
+            mode[node][axis] = 1.0;
+            for (r, pivot) in pivots.iter().enumerate() {
+                let (node, axis) = free[*pivot];
+                mode[node][axis] = -rows[r].0[column];
+            }
+            mode
+        }).collect();
+        Ok((translations, modes))
+    }
+
+    // Position of each joint's rotation in the unknowns vector after the sway mechanisms, `None`
+    // where prescribed
+    fn rotation_unknowns(&self, mechanisms: usize) -> (Vec<Option<usize>>, usize) {
+        let mut count = mechanisms;
+        let unknowns = self.nodes.iter().map(|node| (node.get_rotation_dof() == DofState::Unknown).then(|| {
+            count += 1;
+            count - 1
+        })).collect();
+        (unknowns, count)
+    }
+
+    // Copy the joints into the end nodes of the members, with the deflection of each end taken
+    // square to the member from the sway and vertical deflection of its joint
+    fn sync_member_nodes(&mut self) {
+        for (member, [a, b]) in self.members.iter_mut().zip(&self.connectivity) {
+            let [c, s] = member.get_direction();
+            let [mut start_node, mut end_node] = [self.nodes[*a], self.nodes[*b]];
+            start_node.set_deflection(self.sways[*a] * s + start_node.get_deflection() * c);
+            end_node.set_deflection(self.sways[*b] * s + end_node.get_deflection() * c);
+            member.set_nodes(start_node, end_node);
+        }
+    }
+}
+
+impl FrameBuilder for RigidFrame {
+    fn new(members: Vec<Span>) -> Self {
+        let mut nodes: Vec<Node> = vec![];
+        let mut index_of = |node: Node| {
+            let same = |other: &Node| (other.get_x() - node.get_x()).abs() < 1e-9 && (other.get_y() - node.get_y()).abs() < 1e-9;
+            nodes.iter().position(same).unwrap_or_else(|| {
+                nodes.push(node);
+                nodes.len() - 1
+            })
+        };
+        let connectivity = members
+            .iter()
+            .map(|member| [index_of(member.get_start_node()), index_of(member.get_end_node())])
+            .collect();
+        let joint_moments = vec![0.0; nodes.len()];
+        let joint_loads = vec![[0.0, 0.0]; nodes.len()];
+        let sways = vec![0.0; nodes.len()];
+        RigidFrame { nodes, members, connectivity, joint_moments, joint_loads, sways }
+    }
+
+    fn with_joint_moment(mut self, node: usize, moment: f64) -> Self {
+        self.joint_moments[node] += moment;
+        self
+    }
+
+    fn with_joint_load(mut self, node: usize, horizontal: f64, vertical: f64) -> Self {
+        self.joint_loads[node][0] += horizontal;
+        self.joint_loads[node][1] += vertical;
+        self
+    }
+}
+
+impl FrameDetails for RigidFrame {
+    fn get_nodes(&self) -> &[Node] {
+        &self.nodes
+    }
+
+    fn get_members(&self) -> &[Span] {
+        &self.members
+    }
+
+    fn get_connectivity(&self) -> &[[usize; 2]] {
+        &self.connectivity
+    }
+
+    fn get_joint_moments(&self) -> &[f64] {
+        &self.joint_moments
+    }
+
+    fn get_joint_loads(&self) -> &[[f64; 2]] {
+        &self.joint_loads
+    }
+}
+
+impl FrameTraits for RigidFrame {
+    fn analyze(&mut self) -> Result<(), AnalysisError> {
+        if self.members.is_empty() {
+            return Err(AnalysisError::NoSpans);
+        }
+        if let Some(i) = self.members.iter().position(|member| !member.loads_fit()) {
+            return Err(AnalysisError::LoadOutsideSpan(i));
+        }
+        let (translations, modes) = self.mechanisms()?;
+        let (rotations, count) = self.rotation_unknowns(modes.len());
+
+        // With the unknown displacements cleared, the end actions hold the fixed end actions and
+        // the chord rotation terms from settlements
+        for (i, rotation) in rotations.iter().enumerate() {
+            self.sways[i] = translations[i][0];
+            self.nodes[i].set_deflection(translations[i][1]);
+            if rotation.is_some() {
+                self.nodes[i].set_rotation(0.0);
+            }
+        }
+        self.sync_member_nodes();
+
+        // At every rigid joint the end moments of the beams and columns meeting there balance
+        // the applied couple, and in every sway mechanism the end shears do as much work as the
+        // joint loads
+        let mut matrix = vec![vec![0.0; count]; count];
+        let mut rhs = vec![0.0; count];
+        for (i, node) in self.nodes.iter().enumerate() {
+            let [horizontal, vertical] = self.joint_loads[i];
+            let [translational, rotational] = node.get_support().stiffness();
+            for (k, mode) in modes.iter().enumerate() {
+                rhs[k] += horizontal * mode[i][0] + vertical * mode[i][1];
+                rhs[k] -= translational * mode[i][1] * translations[i][1];
+                for (l, other) in modes.iter().enumerate() {
+                    matrix[k][l] += translational * mode[i][1] * other[i][1];
+                }
+            }
+            if let Some(row) = rotations[i] {
+                rhs[row] += self.joint_moments[i];
+                matrix[row][row] += rotational;
+            }
+        }
+        for (member, [a, b]) in self.members.iter().zip(&self.connectivity) {
+            let stiffness = member.stiffness();
+            let [c, s] = member.get_direction();
+            let [shear_a, shear_b] = member.mes();
+            let [moment_a, moment_b] = member.mem();
+
+            // Each mechanism moves the member end square to itself by its share along the
+            // member's normal, and the end shear pushes the joint back by the same share
+            let columns = |node: usize| -> Vec<(Option<usize>, f64)> {
+                modes.iter().enumerate().map(|(k, mode)| (Some(k), mode[node][0] * s + mode[node][1] * c)).collect()
+            };
+            let dofs = [columns(*a), vec![(rotations[*a], 1.0)], columns(*b), vec![(rotations[*b], 1.0)]];
+            let constants = [-shear_a, moment_a, -shear_b, moment_b];
+            let signs = [-1.0, 1.0, -1.0, 1.0];
+            for r in 0..4 {
+                for (row, row_share) in &dofs[r] {
+                    let Some(row) = row else { continue };
+                    rhs[*row] -= row_share * constants[r];
+                    for (c, column_dofs) in dofs.iter().enumerate() {
+                        for (column, column_share) in column_dofs {
+                            if let Some(column) = column {
+                                matrix[*row][*column] += signs[r] * row_share * stiffness[r][c] * column_share;
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        let solution = cholesky::solve(matrix, rhs)?;
+        for (i, rotation) in rotations.iter().enumerate() {
+            let [sway, deflection] = modes.iter().zip(&solution).fold(translations[i], |[sway, deflection], (mode, amount)| {
+                [sway + amount * mode[i][0], deflection + amount * mode[i][1]]
+            });
+            self.sways[i] = sway;
+            self.nodes[i].set_deflection(deflection);
+            if let Some(index) = rotation {
+                self.nodes[i].set_rotation(solution[*index]);
+            }
+        }
+        self.sync_member_nodes();
+        Ok(())
+    }
+
+    fn get_rotations(&self) -> Vec<f64> {
+        self.nodes.iter().map(|node| node.get_rotation()).collect()
+    }
+
+    fn get_sways(&self) -> Vec<f64> {
+        self.sways.clone()
+    }
+
+    fn get_deflections(&self) -> Vec<f64> {
+        self.nodes.iter().map(|node| node.get_deflection()).collect()
+    }
+
+    fn get_end_moments(&self) -> Vec<[f64; 2]> {
+        self.members.iter().map(|member| member.mem()).collect()
+    }
+
+    fn get_end_shears(&self) -> Vec<[f64; 2]> {
+        self.members.iter().map(|member| member.mes()).collect()
+    }
+}
+
+#[cfg(test)]
+mod rigid_frame_tests {
+    use super::*;
+    use crate::{loads::{distributed_load::DistributedLoad, point_load::PunctualLoad}, test_support::assert_close};
+
+    // Column A-B 4 m high, beam B-C 6 m long, both ends built in
+    fn l_frame(beam_load: f64) -> RigidFrame {
+        let a = Node::new(0.0, 0.0, SupportType::Fixed);
+        let b = Node::at(0.0, 4.0, SupportType::Free);
+        let c = Node::at(6.0, 4.0, SupportType::Fixed);
+        let column = Span::new(a, b, vec![], vec![]).with_ei(1000.0);
+        let beam = Span::new(b, c, vec![DistributedLoad::uniform(beam_load, 0.0, 6.0).unwrap()], vec![]).with_ei(1000.0);
+        RigidFrame::new(vec![column, beam])
+    }
+
+    #[test]
+    fn test_members_share_joints() {
+        let frame = l_frame(0.0);
+        assert_eq!(3, frame.get_nodes().len());
+        assert_eq!(&[[0, 1], [1, 2]], frame.get_connectivity());
+        assert_eq!(4.0, frame.get_members()[0].get_length());
+    }
+
+    #[test]
+    fn test_l_frame_with_udl_on_beam() {
+        let mut frame = l_frame(12.0);
+        frame.analyze().unwrap();
+
+        // 4EI/4 θB + 4EI/6 θB = wL²/12 = 36, so EIθB = 21.6
+        assert_close(0.0216, frame.get_rotations()[1]);
+        let moments = frame.get_end_moments();
+        assert_close(10.8, moments[0][0]);
+        assert_close(21.6, moments[0][1]);
+        assert_close(-21.6, moments[1][0]);
+        assert_close(43.2, moments[1][1]);
+
+        // The column's end moments are held by a pair of shears across it
+        let shears = frame.get_end_shears();
+        assert_close(-8.1, shears[0][0]);
+        assert_close(8.1, shears[0][1]);
+        assert_close(72.0, shears[1][0] + shears[1][1]);
+    }
+
+    #[test]
+    fn test_joint_moment_shared_by_stiffness() {
+        let mut frame = l_frame(0.0).with_joint_moment(1, 100.0);
+        frame.analyze().unwrap();
+
+        // Distribution factors 3/5 to the column and 2/5 to the beam, half carried over
+        let moments = frame.get_end_moments();
+        assert_close(30.0, moments[0][0]);
+        assert_close(60.0, moments[0][1]);
+        assert_close(40.0, moments[1][0]);
+        assert_close(20.0, moments[1][1]);
+    }
+
+    #[test]
+    fn test_frame_without_members() {
+        let mut frame = RigidFrame::new(vec![]);
+        assert_eq!(Err(AnalysisError::NoSpans), frame.analyze());
+    }
+
+    #[test]
+    fn test_settlement_that_shortens_a_member() {
+        let a = Node::new(0.0, 0.0, SupportType::Fixed);
+        let b = Node::new(0.0, 0.01, SupportType::Hinged).with_y(4.0);
+        let mut frame = RigidFrame::new(vec![Span::new(a, b, vec![], vec![]).with_ei(1000.0)]);
+        assert_eq!(Err(AnalysisError::IncompatibleSettlement), frame.analyze());
+    }
+
+    #[test]
+    fn test_portal_frame_sways_under_lateral_load() {
+        let a = Node::new(0.0, 0.0, SupportType::Fixed);
+        let b = Node::at(0.0, 4.0, SupportType::Free);
+        let c = Node::at(6.0, 4.0, SupportType::Free);
+        let d = Node::new(6.0, 0.0, SupportType::Fixed);
+        let left = Span::new(a, b, vec![], vec![]).with_ei(1000.0);
+        let beam = Span::new(b, c, vec![], vec![]).with_ei(1000.0);
+        let right = Span::new(d, c, vec![], vec![]).with_ei(1000.0);
+
+        let mut frame = RigidFrame::new(vec![left, beam, right]).with_joint_load(1, 10.0, 0.0);
+        frame.analyze().unwrap();
+
+        // Antisymmetric sway: θ = 3Δ/16 at both knees and EIΔ/4 = 32/3 from the storey shear
+        let sways = frame.get_sways();
+        assert_close(0.128 / 3.0, sways[1]);
+        assert_close(sways[1], sways[2]);
+        assert_close(0.0, sways[0]);
+        assert_close(0.008, frame.get_rotations()[1]);
+
+        let moments = frame.get_end_moments();
+        assert_close(-12.0, moments[0][0]);
+        assert_close(-8.0, moments[0][1]);
+        assert_close(8.0, moments[1][0]);
+        assert_close(8.0, moments[1][1]);
+        assert_close(-12.0, moments[2][0]);
+        assert_close(-8.0, moments[2][1]);
+
+        // Each column takes half the load, pushed rightward at its top
+        let shears = frame.get_end_shears();
+        assert_close(-5.0, shears[0][1]);
+        assert_close(-5.0, shears[2][1]);
+    }
+
+    #[test]
+    fn test_column_load_and_overhanging_beam() {
+        let a = Node::new(0.0, 0.0, SupportType::Fixed);
+        let b = Node::at(0.0, 4.0, SupportType::Free);
+        let c = Node::at(3.0, 4.0, SupportType::Free);
+        let column = Span::new(a, b, vec![], vec![]).with_ei(1000.0);
+        let beam = Span::new(b, c, vec![], vec![PunctualLoad { value: 10.0, x: 3.0 }]).with_ei(1000.0);
+
+        let mut frame = RigidFrame::new(vec![column, beam]);
+        frame.analyze().unwrap();
+
+        // The column takes the cantilever moment PL with no shear, bending uniformly
+        let moments = frame.get_end_moments();
+        assert_close(-30.0, moments[0][0]);
+        assert_close(30.0, moments[0][1]);
+        assert_close(-30.0, moments[1][0]);
+        assert_close(0.0, moments[1][1]);
+
+        // Knee rotation Mh/EI and sway Mh²/2EI, the tip adds PL³/3EI to the knee rotation
+        assert_close(0.12, frame.get_rotations()[1]);
+        assert_close(0.24, frame.get_sways()[1]);
+        assert_close(0.0, frame.get_deflections()[1]);
+        assert_close(0.45, frame.get_deflections()[2]);
+
+        // A lateral load along the column is resisted at its base and knee
+        let column = Span::new(a, b, vec![DistributedLoad::uniform(5.0, 0.0, 4.0).unwrap()], vec![]).with_ei(1000.0);
+        let mut frame = RigidFrame::new(vec![column]);
+        frame.analyze().unwrap();
+        assert_close(20.0, frame.get_end_shears()[0][0]);
+        assert_close(0.0, frame.get_end_shears()[0][1]);
+        assert_close(-40.0, frame.get_end_moments()[0][0]);
+        assert_close(0.0, frame.get_end_moments()[0][1]);
+    }
+}
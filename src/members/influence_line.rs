@@ -0,0 +1,232 @@
+use crate::{
+    loads::{load_case::{LoadCase, LoadCaseKind}, point_load::PunctualLoad},
+    nodes::node::NodeTraits,
+    spans::{diagram::stations, traits::{SpanDetails, SpanTraits}},
+};
+use super::{beam::ContinuousBeam, error::AnalysisError, traits::{BeamBuilder, BeamDetails, BeamTraits}};
+
+// Response of the beam followed as the unit load moves, sections measured from the first node
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum InfluenceQuantity {
+    // Bending moment at a section, sagging positive
+    Moment(f64),
+    // Shear force just left of a section
+    ShearLeft(f64),
+    // Shear force just right of a section
+    ShearRight(f64),
+    // Vertical reaction at a node, upward positive
+    Reaction(usize),
+    // Moment reaction at a node, clockwise positive
+    ReactionMoment(usize),
+}
+
+// Value of the quantity with the unit load at x
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct InfluencePoint {
+    pub x: f64,
+    pub value: f64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct InfluenceLine {
+    pub quantity: InfluenceQuantity,
+    pub points: Vec<InfluencePoint>,
+}
+
+// Influence lines of a continuous beam, found by moving a unit downward load from station to
+// station and solving each position against the same factored stiffness matrix
+#[derive(Debug, Clone)]
+pub struct InfluenceLines {
+    beam: ContinuousBeam,
+    // Evenly spaced load positions per span
+    points: usize,
+}
+
+impl InfluenceLines {
+    pub fn new(beam: ContinuousBeam) -> Self {
+        InfluenceLines { beam: beam.unloaded(), points: 21 }
+    }
+
+    pub fn with_points(mut self, points: usize) -> Self {
+        self.points = points;
+        self
+    }
+
+    // Span holding a section and the station within it. A section at a support belongs to the span
+    // on its left, or on its right when `right` is set
+    fn locate(&self, x: f64, right: bool) -> (usize, f64) {
+        let spans = self.beam.get_spans();
+        let index = spans
+            .iter()
+            .position(|span| match right {
+                true => x < span.get_end_node().get_x() - 1e-12,
+                false => x <= span.get_end_node().get_x() + 1e-12,
+            })
+            .unwrap_or(spans.len().saturating_sub(1));
+        (index, x - spans[index].get_start_node().get_x())
+    }
+
+    // Every section on the beam and every node one of its own
+    fn check_quantities(&self, quantities: &[InfluenceQuantity]) -> Result<(), AnalysisError> {
+        let spans = self.beam.get_spans();
+        let (Some(first), Some(last)) = (spans.first(), spans.last()) else {
+            return Err(AnalysisError::NoSpans);
+        };
+        let [start, end] = [first.get_start_node().get_x(), last.get_end_node().get_x()];
+        for quantity in quantities {
+            match *quantity {
+                InfluenceQuantity::Moment(x) | InfluenceQuantity::ShearLeft(x) | InfluenceQuantity::ShearRight(x) => {
+                    if !(start - 1e-12..=end + 1e-12).contains(&x) {
+                        return Err(AnalysisError::SectionOutsideBeam(x));
+                    }
+                }
+                InfluenceQuantity::Reaction(node) | InfluenceQuantity::ReactionMoment(node) => {
+                    if node >= self.beam.get_nodes().len() {
+                        return Err(AnalysisError::UnknownNode(node));
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    // Load positions as (span, station) pairs, every support taken once and every section added
+    fn positions(&self, quantities: &[InfluenceQuantity]) -> Vec<(usize, f64)> {
+        let sections: Vec<f64> = quantities.iter().filter_map(|quantity| match quantity {
+            InfluenceQuantity::Moment(x) | InfluenceQuantity::ShearLeft(x) | InfluenceQuantity::ShearRight(x) => Some(*x),
+            _ => None,
+        }).collect();
+
+        self.beam.get_spans().iter().enumerate().flat_map(|(i, span)| {
+            let offset = span.get_start_node().get_x();
+            let extra = sections.iter().map(move |x| x - offset);
+            stations(span.get_length(), self.points, extra)
+                .into_iter()
+                .filter(move |&x| i == 0 || x > 1e-12)
+                .map(move |x| (i, x))
+        }).collect()
+    }
+
+    fn evaluate(&self, beam: &ContinuousBeam, quantity: InfluenceQuantity) -> f64 {
+        match quantity {
+            InfluenceQuantity::Moment(x) => {
+                let (i, x) = self.locate(x, false);
+                beam.get_spans()[i].moment_at(x)
+            }
+            // Off the ends of the beam there is nothing left to shear
+            InfluenceQuantity::ShearLeft(x) => match self.locate(x, false) {
+                (0, x) if x <= 1e-12 => 0.0,
+                (i, x) => beam.get_spans()[i].shear_before(x),
+            },
+            InfluenceQuantity::ShearRight(x) => match self.locate(x, true) {
+                (i, x) if i + 1 == beam.get_spans().len() && x >= beam.get_spans()[i].get_length() - 1e-12 => 0.0,
+                (i, x) => beam.get_spans()[i].shear_at(x),
+            },
+            InfluenceQuantity::Reaction(node) => beam.get_reactions()[node].force,
+            InfluenceQuantity::ReactionMoment(node) => beam.get_reactions()[node].moment,
+        }
+    }
+
+    // Influence line of every quantity, sampled at the same load positions
+    pub fn compute(&self, quantities: &[InfluenceQuantity]) -> Result<Vec<InfluenceLine>, AnalysisError> {
+        self.check_quantities(quantities)?;
+        let factors = self.beam.factorize()?;
+        let mut lines: Vec<InfluenceLine> = quantities.iter().map(|&quantity| InfluenceLine { quantity, points: vec![] }).collect();
+
+        for (span, x) in self.positions(quantities) {
+            let (built, station) = self.beam.built_station(span, x);
+            let unit = LoadCase::new("Unit", LoadCaseKind::Other).with_punctual_load(built, PunctualLoad { value: 1.0, x: station });
+            let mut beam = self.beam.clone().with_load_case(&unit);
+            beam.analyze_with(&factors)?;
+            let position = x + beam.get_spans()[span].get_start_node().get_x();
+            for line in lines.iter_mut() {
+                let value = self.evaluate(&beam, line.quantity);
+                line.points.push(InfluencePoint { x: position, value });
+            }
+        }
+        Ok(lines)
+    }
+
+    pub fn influence_line(&self, quantity: InfluenceQuantity) -> Result<InfluenceLine, AnalysisError> {
+        Ok(self.compute(&[quantity])?.remove(0))
+    }
+}
+
+#[cfg(test)]
+mod influence_line_tests {
+    use super::*;
+    use crate::test_support::{assert_close, two_spans};
+
+    fn two_span_beam() -> ContinuousBeam {
+        ContinuousBeam::new(two_spans().to_vec())
+    }
+
+    fn value_at(line: &InfluenceLine, x: f64) -> f64 {
+        line.points.iter().find(|point| (point.x - x).abs() < 1e-9).unwrap().value
+    }
+
+    #[test]
+    fn test_support_moment_and_reaction() {
+        let lines = InfluenceLines::new(two_span_beam())
+            .with_points(7)
+            .compute(&[InfluenceQuantity::Moment(6.0), InfluenceQuantity::Reaction(0), InfluenceQuantity::Moment(3.0)])
+            .unwrap();
+        assert_eq!(13, lines[0].points.len());
+
+        // M_B = -a(L² - a²)/4L² for a unit load at a in either span
+        assert_close(-0.5625, value_at(&lines[0], 3.0));
+        assert_close(-0.5625, value_at(&lines[0], 9.0));
+        assert_close(0.0, value_at(&lines[0], 6.0));
+        assert_close(0.0, value_at(&lines[0], 12.0));
+
+        assert_close(1.0, value_at(&lines[1], 0.0));
+        assert_close(0.40625, value_at(&lines[1], 3.0));
+        assert_close(-0.09375, value_at(&lines[1], 9.0));
+
+        // Simple span triangle less half the support moment
+        assert_close(1.21875, value_at(&lines[2], 3.0));
+    }
+
+    #[test]
+    fn test_shear_either_side_of_support() {
+        let lines = InfluenceLines::new(two_span_beam())
+            .with_points(7)
+            .compute(&[InfluenceQuantity::ShearLeft(6.0), InfluenceQuantity::ShearRight(6.0)])
+            .unwrap();
+
+        // A load in the far span only reaches the near span through M_B
+        assert_close(-0.09375, value_at(&lines[0], 9.0));
+        assert_close(0.59375, value_at(&lines[1], 9.0));
+        assert_close(-0.59375, value_at(&lines[0], 3.0));
+        // A load over the support goes straight into it
+        assert_close(0.0, value_at(&lines[0], 6.0));
+        assert_close(0.0, value_at(&lines[1], 6.0));
+    }
+
+    #[test]
+    fn test_section_added_to_stations() {
+        let line = InfluenceLines::new(two_span_beam())
+            .with_points(3)
+            .influence_line(InfluenceQuantity::ShearRight(2.0))
+            .unwrap();
+        assert!(line.points.iter().any(|point| (point.x - 2.0).abs() < 1e-12));
+        assert!(line.points.windows(2).all(|pair| pair[0].x < pair[1].x));
+    }
+
+    #[test]
+    fn test_unknown_node_and_section_off_the_beam() {
+        let lines = InfluenceLines::new(two_span_beam());
+        assert_eq!(Err(AnalysisError::UnknownNode(7)), lines.influence_line(InfluenceQuantity::Reaction(7)));
+        assert_eq!(Err(AnalysisError::UnknownNode(3)), lines.influence_line(InfluenceQuantity::ReactionMoment(3)));
+        assert_eq!(Err(AnalysisError::SectionOutsideBeam(50.0)), lines.influence_line(InfluenceQuantity::Moment(50.0)));
+        assert_eq!(Err(AnalysisError::SectionOutsideBeam(-1.0)), lines.influence_line(InfluenceQuantity::ShearRight(-1.0)));
+        assert!(lines.influence_line(InfluenceQuantity::Reaction(2)).is_ok());
+        assert!(lines.influence_line(InfluenceQuantity::ShearLeft(12.0)).is_ok());
+
+        // Just left of the first node and just right of the last one the beam carries no shear
+        for quantity in [InfluenceQuantity::ShearLeft(0.0), InfluenceQuantity::ShearRight(12.0)] {
+            let line = lines.influence_line(quantity).unwrap();
+            assert!(line.points.iter().all(|point| point.value == 0.0));
+        }
+    }
+}
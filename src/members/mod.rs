@@ -1,2 +1,9 @@
 pub mod beam;
-pub mod frame;
\ No newline at end of file
+pub mod error;
+pub mod frame;
+pub mod influence_line;
+pub mod model;
+pub mod moment_distribution;
+pub mod pattern_loading;
+pub mod reaction;
+pub mod traits;
\ No newline at end of file
@@ -0,0 +1,291 @@
+use crate::loads::{load_case::LoadCase, load_combination::LoadCombination};
+use super::{beam::ContinuousBeam, error::AnalysisError, traits::BeamBuilder};
+
+// Continuous beam analysed under several named load cases. The geometry, supports and stiffness
+// are set up once, loads built into the spans are left out, and every case is solved against the
+// same factored stiffness matrix and kept as a beam of its own. Combinations are not solved but
+// superposed from the case results
+#[derive(Debug, Clone)]
+pub struct BeamModel {
+    beam: ContinuousBeam,
+    cases: Vec<LoadCase>,
+    results: Vec<ContinuousBeam>,
+    combinations: Vec<LoadCombination>,
+    combination_results: Vec<ContinuousBeam>,
+}
+
+impl BeamModel {
+    pub fn new(beam: ContinuousBeam) -> Self {
+        BeamModel {
+            beam: beam.unloaded(),
+            cases: vec![],
+            results: vec![],
+            combinations: vec![],
+            combination_results: vec![],
+        }
+    }
+
+    pub fn with_load_case(mut self, case: LoadCase) -> Self {
+        self.cases.push(case);
+        self
+    }
+
+    pub fn with_combination(mut self, combination: LoadCombination) -> Self {
+        self.combinations.push(combination);
+        self
+    }
+
+    pub fn with_combinations(mut self, combinations: Vec<LoadCombination>) -> Self {
+        self.combinations.extend(combinations);
+        self
+    }
+
+    // The beam without any loads
+    pub fn get_beam(&self) -> &ContinuousBeam {
+        &self.beam
+    }
+
+    pub fn get_load_cases(&self) -> &[LoadCase] {
+        &self.cases
+    }
+
+    pub fn get_combinations(&self) -> &[LoadCombination] {
+        &self.combinations
+    }
+
+    // The beam carrying the factored loads of every case in the combination, displaced by the
+    // factored sum of their solutions. End actions, reactions and diagrams follow linearly
+    fn combine(&self, combination: &LoadCombination) -> Option<ContinuousBeam> {
+        let terms = combination.get_factors().iter().map(|(name, factor)| {
+            let i = self.cases.iter().position(|case| case.get_name() == name)?;
+            Some((*factor, i))
+        }).collect::<Option<Vec<_>>>()?;
+
+        let mut beam = terms.iter().fold(self.beam.clone(), |beam, (factor, i)| {
+            beam.with_load_case(&self.cases[*i].scaled(*factor))
+        });
+        let results: Vec<(f64, &ContinuousBeam)> = terms.iter().map(|(factor, i)| (*factor, &self.results[*i])).collect();
+        beam.superpose(&results);
+        Some(beam)
+    }
+
+    pub fn analyze(&mut self) -> Result<(), AnalysisError> {
+        for case in &self.cases {
+            self.beam.check_load_case(case)?;
+        }
+        let factors = self.beam.factorize()?;
+        self.results = self.cases.iter().map(|case| {
+            let mut beam = self.beam.clone().with_load_case(case);
+            beam.analyze_with(&factors)?;
+            Ok(beam)
+        }).collect::<Result<_, AnalysisError>>()?;
+        self.combination_results = self.combinations.iter().enumerate().map(|(i, combination)| {
+            self.combine(combination).ok_or(AnalysisError::UnknownLoadCase(i))
+        }).collect::<Result<_, AnalysisError>>()?;
+        Ok(())
+    }
+
+    // Analysed beam of every load case, in the order the cases were added
+    pub fn get_results(&self) -> &[ContinuousBeam] {
+        &self.results
+    }
+
+    pub fn get_result(&self, name: &str) -> Option<&ContinuousBeam> {
+        self.cases.iter().position(|case| case.get_name() == name).and_then(|i| self.results.get(i))
+    }
+
+    // Superposed beam of every combination, in the order the combinations were added
+    pub fn get_combination_results(&self) -> &[ContinuousBeam] {
+        &self.combination_results
+    }
+
+    pub fn get_combination_result(&self, name: &str) -> Option<&ContinuousBeam> {
+        self.combinations
+            .iter()
+            .position(|combination| combination.get_name() == name)
+            .and_then(|i| self.combination_results.get(i))
+    }
+}
+
+#[cfg(test)]
+mod beam_model_tests {
+    use super::*;
+    use crate::{
+        loads::{distributed_load::DistributedLoad, load_case::LoadCaseKind, load_combination::DesignCode, point_load::PunctualLoad},
+        members::traits::{BeamDetails, BeamTraits},
+        nodes::node::{Node, NodeTraits, SupportType},
+        spans::{span::Span, traits::{SpanBuilder, SpanDetails}},
+        test_support::{assert_close, two_spans},
+    };
+
+    fn assert_same_moments(expected: &ContinuousBeam, actual: &ContinuousBeam) {
+        for (expected, actual) in expected.get_end_moments().iter().zip(actual.get_end_moments()) {
+            assert_close(expected[0], actual[0]);
+            assert_close(expected[1], actual[1]);
+        }
+    }
+
+    #[test]
+    fn test_cases_solved_separately() {
+        let dead = LoadCase::new("Dead", LoadCaseKind::Dead)
+            .with_distributed_load(0, DistributedLoad::uniform(10.0, 0.0, 6.0).unwrap())
+            .with_distributed_load(1, DistributedLoad::uniform(10.0, 0.0, 6.0).unwrap());
+        let live = LoadCase::new("Live", LoadCaseKind::Live)
+            .with_punctual_load(1, PunctualLoad { value: 50.0, x: 3.0 });
+
+        // Loads built into the spans are not part of any case
+        let [ab, bc] = two_spans();
+        let beam = ContinuousBeam::new(vec![ab.with_loads(vec![DistributedLoad::uniform(99.0, 0.0, 6.0).unwrap()], vec![]), bc]);
+        let mut model = BeamModel::new(beam).with_load_case(dead).with_load_case(live);
+        model.analyze().unwrap();
+        assert_eq!(2, model.get_results().len());
+        assert!(model.get_beam().get_spans()[0].get_distributed_loads().is_empty());
+
+        let [ab, bc] = two_spans();
+        let mut dead_only = ContinuousBeam::new(vec![
+            ab.with_loads(vec![DistributedLoad::uniform(10.0, 0.0, 6.0).unwrap()], vec![]),
+            bc.with_loads(vec![DistributedLoad::uniform(10.0, 0.0, 6.0).unwrap()], vec![]),
+        ]);
+        dead_only.analyze().unwrap();
+        assert_same_moments(&dead_only, model.get_result("Dead").unwrap());
+
+        let [ab, bc] = two_spans();
+        let mut live_only = ContinuousBeam::new(vec![ab, bc.with_loads(vec![], vec![PunctualLoad { value: 50.0, x: 3.0 }])]);
+        live_only.analyze().unwrap();
+        assert_same_moments(&live_only, model.get_result("Live").unwrap());
+        assert!(model.get_result("Wind").is_none());
+    }
+
+    #[test]
+    fn test_settlement_case() {
+        let settlement = LoadCase::new("Settlement", LoadCaseKind::Settlement).with_settlement(1, 0.01);
+        let mut model = BeamModel::new(ContinuousBeam::new(two_spans().to_vec())).with_load_case(settlement);
+        model.analyze().unwrap();
+
+        let result = model.get_result("Settlement").unwrap();
+        assert_eq!(0.01, result.get_deflections()[1]);
+        let mut settled = ContinuousBeam::new(two_spans().to_vec()).with_settlement(1, 0.01);
+        settled.analyze().unwrap();
+        assert_same_moments(&settled, result);
+        assert!(result.get_end_moments()[0][1] != 0.0);
+    }
+
+    #[test]
+    fn test_combinations_superpose_case_results() {
+        let dead = LoadCase::new("Dead", LoadCaseKind::Dead)
+            .with_distributed_load(0, DistributedLoad::uniform(10.0, 0.0, 6.0).unwrap())
+            .with_distributed_load(1, DistributedLoad::uniform(10.0, 0.0, 6.0).unwrap());
+        let live = LoadCase::new("Live", LoadCaseKind::Live)
+            .with_punctual_load(1, PunctualLoad { value: 50.0, x: 3.0 })
+            .with_joint_moment(1, 20.0);
+        let cases = vec![dead.clone(), live.clone()];
+        let mut model = BeamModel::new(ContinuousBeam::new(two_spans().to_vec()))
+            .with_load_case(dead)
+            .with_load_case(live)
+            .with_combinations(DesignCode::Bs8110.combinations(&cases))
+            .with_combination(LoadCombination::new("Service").with_factor("Dead", 1.0).with_factor("Live", 1.0));
+        model.analyze().unwrap();
+        assert_eq!(3, model.get_combination_results().len());
+
+        // The superposed results match the factored loads solved directly
+        let combined = model.get_combination_result("1.4G + 1.6Q").unwrap();
+        let [ab, bc] = two_spans();
+        let mut solved = ContinuousBeam::new(vec![
+            ab.with_loads(vec![DistributedLoad::uniform(14.0, 0.0, 6.0).unwrap()], vec![]),
+            bc.with_loads(vec![DistributedLoad::uniform(14.0, 0.0, 6.0).unwrap()], vec![PunctualLoad { value: 80.0, x: 3.0 }]),
+        ]).with_joint_moment(1, 32.0);
+        solved.analyze().unwrap();
+        assert_same_moments(&solved, combined);
+        for (expected, actual) in solved.get_reactions().iter().zip(combined.get_reactions()) {
+            assert_close(expected.force, actual.force);
+            assert_close(expected.moment, actual.moment);
+        }
+        for (expected, actual) in solved.get_diagram(10).iter().zip(combined.get_diagram(10)) {
+            assert_close(expected.moment, actual.moment);
+            assert_close(expected.shear, actual.shear);
+        }
+        assert!(combined.check_equilibrium().is_satisfied(1e-9));
+
+        // Moments of a combination are the factored sum of those of the cases
+        let dead_moments = model.get_result("Dead").unwrap().get_end_moments();
+        let live_moments = model.get_result("Live").unwrap().get_end_moments();
+        let service = model.get_combination_result("Service").unwrap().get_end_moments();
+        assert_close(dead_moments[0][1] + live_moments[0][1], service[0][1]);
+    }
+
+    #[test]
+    fn test_combination_of_unknown_case() {
+        let mut model = BeamModel::new(ContinuousBeam::new(two_spans().to_vec()))
+            .with_load_case(LoadCase::new("Dead", LoadCaseKind::Dead))
+            .with_combination(LoadCombination::new("1.4G + 1.6Q").with_factor("Dead", 1.4).with_factor("Imposed", 1.6));
+        assert_eq!(Err(AnalysisError::UnknownLoadCase(0)), model.analyze());
+    }
+
+    #[test]
+    fn test_unstable_model() {
+        let a = Node::new(0.0, 0.0, SupportType::Roller);
+        let b = Node::new(4.0, 0.0, SupportType::Free);
+        let mut model = BeamModel::new(ContinuousBeam::new(vec![Span::new(a, b, vec![], vec![])]))
+            .with_load_case(LoadCase::new("Dead", LoadCaseKind::Dead));
+        assert_eq!(Err(AnalysisError::Unstable), model.analyze());
+    }
+
+    #[test]
+    fn test_case_off_the_beam() {
+        let beam = ContinuousBeam::new(two_spans().to_vec());
+        let analyze = |case: LoadCase| BeamModel::new(beam.clone()).with_load_case(case).analyze();
+
+        let span = LoadCase::new("Dead", LoadCaseKind::Dead).with_distributed_load(5, DistributedLoad::uniform(10.0, 0.0, 6.0).unwrap());
+        assert_eq!(Err(AnalysisError::UnknownSpan(5)), analyze(span.clone()));
+        let joint = LoadCase::new("Dead", LoadCaseKind::Dead).with_joint_moment(9, 20.0);
+        assert_eq!(Err(AnalysisError::UnknownNode(9)), analyze(joint));
+        let settlement = LoadCase::new("Settlement", LoadCaseKind::Settlement).with_settlement(3, 0.01);
+        assert_eq!(Err(AnalysisError::UnknownNode(3)), analyze(settlement));
+
+        // Added to the beam directly, the case is reported by the analysis
+        let mut loaded = beam.clone().with_load_case(&span);
+        assert_eq!(Err(AnalysisError::UnknownSpan(5)), loaded.analyze());
+    }
+
+    #[test]
+    fn test_settlement_of_a_free_node() {
+        let a = Node::new(0.0, 0.0, SupportType::Fixed);
+        let b = Node::new(4.0, 0.0, SupportType::Free);
+        let settlement = LoadCase::new("Settlement", LoadCaseKind::Settlement).with_settlement(1, 0.01);
+        let mut model = BeamModel::new(ContinuousBeam::new(vec![Span::new(a, b, vec![], vec![])])).with_load_case(settlement);
+        assert_eq!(Err(AnalysisError::UnsupportedSettlement(1)), model.analyze());
+    }
+
+    #[test]
+    fn test_case_on_spans_cut_by_a_hinge() {
+        let a = Node::new(0.0, 0.0, SupportType::Fixed);
+        let [ab, bc] = two_spans();
+        let ab = Span::new(a, ab.get_end_node(), vec![], vec![]);
+        let udl = DistributedLoad::uniform(5.0, 0.0, 6.0).unwrap();
+        let point = PunctualLoad { value: 20.0, x: 4.0 };
+        let case = LoadCase::new("Dead", LoadCaseKind::Dead)
+            .with_distributed_load(0, udl)
+            .with_punctual_load(0, point)
+            .with_distributed_load(1, DistributedLoad::uniform(10.0, 0.0, 6.0).unwrap())
+            .with_joint_moment(1, 15.0)
+            .with_settlement(2, 0.01);
+
+        // Span 1 and node 1 are still the second span and the support between them
+        let beam = ContinuousBeam::new(vec![ab.clone(), bc.clone()]).with_hinge_at(3.0);
+        let mut model = BeamModel::new(beam).with_load_case(case);
+        model.analyze().unwrap();
+        let result = model.get_result("Dead").unwrap();
+        assert_eq!(3, result.get_spans().len());
+
+        let mut solved = ContinuousBeam::new(vec![
+            ab.with_loads(vec![udl], vec![point]),
+            bc.with_loads(vec![DistributedLoad::uniform(10.0, 0.0, 6.0).unwrap()], vec![]),
+        ])
+            .with_joint_moment(1, 15.0)
+            .with_settlement(2, 0.01)
+            .with_hinge_at(3.0);
+        solved.analyze().unwrap();
+        assert_same_moments(&solved, result);
+        assert_eq!(0.01, result.get_deflections()[3]);
+    }
+}
@@ -0,0 +1,402 @@
+use std::fmt;
+
+use crate::{
+    nodes::node::{DofState, Node, NodeTraits, SupportType},
+    spans::{span::Span, traits::{SpanBuilder, SpanDetails, SpanTraits}},
+};
+use super::{
+    beam::ContinuousBeam,
+    error::AnalysisError,
+    traits::BeamDetails,
+};
+
+// Step of the distribution a row of the table records
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Step {
+    FixedEnd,
+    Balance(usize),
+    CarryOver(usize),
+}
+
+// Moment added to every span end by one step, in the order of the spans
+#[derive(Debug, Clone, PartialEq)]
+pub struct DistributionRow {
+    pub step: Step,
+    pub moments: Vec<[f64; 2]>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DistributionTable {
+    // Rotational stiffness 4EI/L of every span end, 3EI/L next to a pinned end of the beam and zero
+    // at the pinned end itself or along a cantilever
+    pub stiffness: Vec<[f64; 2]>,
+    pub distribution_factors: Vec<[f64; 2]>,
+    // Carried over between two held ends, nothing is carried over to a pinned end
+    pub carry_over_factor: f64,
+    pub rows: Vec<DistributionRow>,
+    // Member End Moments once every joint balances, clockwise positive
+    pub final_moments: Vec<[f64; 2]>,
+    pub cycles: usize,
+}
+
+// Hardy Cross moment distribution over the spans and nodes of a continuous beam. Every support
+// must hold its node vertically, apart from the free end of an overhang. Simple supports at the
+// ends of the beam stay pinned throughout, with modified stiffness and fixed end moments next to them
+#[derive(Debug, Clone)]
+pub struct MomentDistribution {
+    nodes: Vec<Node>,
+    spans: Vec<Span>,
+    joint_moments: Vec<f64>,
+    tolerance: f64,
+    max_cycles: usize,
+}
+
+// Whether the node is the unsupported tip of a cantilever
+fn is_free(node: &Node) -> bool {
+    matches!(node.get_support(), SupportType::Free)
+}
+
+impl MomentDistribution {
+    pub fn new(beam: &ContinuousBeam) -> Self {
+        MomentDistribution {
+            nodes: beam.get_nodes().to_vec(),
+            spans: beam.get_spans().to_vec(),
+            joint_moments: beam.get_joint_moments().to_vec(),
+            tolerance: 1e-6,
+            max_cycles: 100,
+        }
+    }
+
+    // Largest unbalanced moment left at any joint once the distribution stops
+    pub fn with_tolerance(self, tolerance: f64) -> Self {
+        MomentDistribution { tolerance, ..self }
+    }
+
+    pub fn with_max_cycles(self, max_cycles: usize) -> Self {
+        MomentDistribution { max_cycles, ..self }
+    }
+
+    // Whether node `i` is a simple support at either end of the beam, carrying no moment
+    fn is_pinned_end(&self, i: usize) -> bool {
+        let node = &self.nodes[i];
+        (i == 0 || i == self.nodes.len() - 1)
+            && node.get_translation_dof() == DofState::Prescribed
+            && node.get_rotation_dof() == DofState::Unknown
+            && node.get_support().stiffness()[1] == 0.0
+    }
+
+    // Couple applied at node `i` where it is a pinned or free end of the beam
+    fn end_couple(&self, i: usize) -> f64 {
+        let end = i == 0 || i == self.nodes.len() - 1;
+        match end && (is_free(&self.nodes[i]) || self.is_pinned_end(i)) {
+            true => self.joint_moments[i],
+            false => 0.0,
+        }
+    }
+
+    // Nodes released and balanced in every cycle, rejecting the nodes the method cannot handle
+    fn balanced_nodes(&self) -> Result<Vec<bool>, AnalysisError> {
+        let last = self.nodes.len() - 1;
+        self.nodes.iter().enumerate().map(|(i, node)| {
+            let overhang = is_free(node) && (i == 0 || i == last);
+            let sways = node.get_translation_dof() == DofState::Unknown && !overhang;
+            if sways || node.has_moment_release() {
+                return Err(AnalysisError::UnsupportedNode(i));
+            }
+            Ok(!overhang && !self.is_pinned_end(i) && node.get_rotation_dof() == DofState::Unknown)
+        }).collect()
+    }
+
+    // Moments holding every balanced joint still. Span `i` sees those joints as fixed ends, so
+    // its loads give the modified moments next to a pinned or free end of the beam. A couple at a
+    // pinned or free end of the beam is never balanced, it goes straight into the span end there
+    // and half of it, or all of it against a free tip, on to the held end
+    fn fixed_end_moments(&self, i: usize) -> [f64; 2] {
+        let span = &self.spans[i];
+        let hold = |j: usize, node: Node| {
+            let support = if is_free(&node) {
+                SupportType::Free
+            } else if self.is_pinned_end(j) {
+                SupportType::Hinged
+            } else {
+                SupportType::Fixed
+            };
+            Node::new(node.get_x(), node.get_deflection(), support).with_y(node.get_y())
+        };
+        let mut locked = span.clone();
+        locked.set_nodes(hold(i, span.get_start_node()), hold(i + 1, span.get_end_node()));
+
+        let k = span.get_ei() * span.chord_rotation() / span.get_length();
+        let settlement = match (self.is_pinned_end(i), self.is_pinned_end(i + 1)) {
+            _ if is_free(&span.get_start_node()) || is_free(&span.get_end_node()) => [0.0, 0.0],
+            (false, false) => [-6.0 * k, -6.0 * k],
+            (true, false) => [0.0, -3.0 * k],
+            (false, true) => [-3.0 * k, 0.0],
+            (true, true) => [0.0, 0.0],
+        };
+        let [couple_a, couple_b] = [self.end_couple(i), self.end_couple(i + 1)];
+        let couples = match (is_free(&span.get_start_node()), is_free(&span.get_end_node())) {
+            (true, _) => [couple_a, -couple_a],
+            (_, true) => [-couple_b, couple_b],
+            _ => {
+                let carried = |couple: f64, far: usize| if self.is_pinned_end(far) { 0.0 } else { 0.5 * couple };
+                [couple_a + carried(couple_b, i), couple_b + carried(couple_a, i + 1)]
+            }
+        };
+        let [fem_a, fem_b] = locked.modified_fem();
+        [fem_a + settlement[0] + couples[0], fem_b + settlement[1] + couples[1]]
+    }
+
+    pub fn distribute(&self) -> Result<DistributionTable, AnalysisError> {
+        if self.spans.is_empty() {
+            return Err(AnalysisError::NoSpans);
+        }
+        let balanced = self.balanced_nodes()?;
+        let carry_over_factor = 0.5;
+
+        let pinned: Vec<bool> = (0..self.nodes.len()).map(|i| self.is_pinned_end(i)).collect();
+        let stiffness: Vec<[f64; 2]> = self.spans.iter().enumerate().map(|(i, span)| {
+            let k = span.get_ei() / span.get_length();
+            match (pinned[i], pinned[i + 1]) {
+                _ if is_free(&span.get_start_node()) || is_free(&span.get_end_node()) => [0.0, 0.0],
+                (false, false) => [4.0 * k, 4.0 * k],
+                (true, false) => [0.0, 3.0 * k],
+                (false, true) => [3.0 * k, 0.0],
+                (true, true) => [0.0, 0.0],
+            }
+        }).collect();
+
+        // A joint shares out its unbalanced moment among the span ends meeting there and its
+        // rotational spring, if any
+        let spring: Vec<f64> = self.nodes.iter().map(|node| node.get_support().stiffness()[1]).collect();
+        let joint_stiffness: Vec<f64> = (0..self.nodes.len()).map(|j| {
+            let left = if j > 0 { stiffness[j - 1][1] } else { 0.0 };
+            let right = stiffness.get(j).map_or(0.0, |ends| ends[0]);
+            left + right + spring[j]
+        }).collect();
+        let factor = |j: usize, k: f64| if balanced[j] && joint_stiffness[j] > 0.0 { k / joint_stiffness[j] } else { 0.0 };
+        let distribution_factors: Vec<[f64; 2]> = stiffness.iter().enumerate()
+            .map(|(i, [k_a, k_b])| [factor(i, *k_a), factor(i + 1, *k_b)])
+            .collect();
+
+        let fixed_end: Vec<[f64; 2]> = (0..self.spans.len()).map(|i| self.fixed_end_moments(i)).collect();
+        let mut moments = fixed_end.clone();
+        let mut spring_moments = vec![0.0; self.nodes.len()];
+        let mut rows = vec![DistributionRow { step: Step::FixedEnd, moments: fixed_end }];
+
+        let unbalanced = |moments: &[[f64; 2]], spring_moments: &[f64]| -> Vec<f64> {
+            (0..self.nodes.len()).map(|j| {
+                if !balanced[j] {
+                    return 0.0;
+                }
+                let left = if j > 0 { moments[j - 1][1] } else { 0.0 };
+                let right = moments.get(j).map_or(0.0, |ends| ends[0]);
+                self.joint_moments[j] - left - right - spring_moments[j]
+            }).collect()
+        };
+
+        let mut cycles = 0;
+        loop {
+            let residual = unbalanced(&moments, &spring_moments);
+            if residual.iter().all(|value| value.abs() <= self.tolerance) {
+                break;
+            }
+            if cycles == self.max_cycles {
+                return Err(AnalysisError::NotConverged(cycles));
+            }
+            cycles += 1;
+
+            let balance: Vec<[f64; 2]> = distribution_factors.iter().enumerate()
+                .map(|(i, [df_a, df_b])| [df_a * residual[i], df_b * residual[i + 1]])
+                .collect();
+            for (j, value) in residual.iter().enumerate() {
+                spring_moments[j] += factor(j, spring[j]) * value;
+            }
+            let carry_over: Vec<[f64; 2]> = balance.iter().enumerate()
+                .map(|(i, [balance_a, balance_b])| {
+                    let carried = |pinned: bool, balance: f64| if pinned { 0.0 } else { carry_over_factor * balance };
+                    [carried(pinned[i], *balance_b), carried(pinned[i + 1], *balance_a)]
+                })
+                .collect();
+
+            for (row, step) in [(balance, Step::Balance(cycles)), (carry_over, Step::CarryOver(cycles))] {
+                for (ends, added) in moments.iter_mut().zip(&row) {
+                    ends[0] += added[0];
+                    ends[1] += added[1];
+                }
+                rows.push(DistributionRow { step, moments: row });
+            }
+        }
+
+        Ok(DistributionTable { stiffness, distribution_factors, carry_over_factor, rows, final_moments: moments, cycles })
+    }
+}
+
+impl fmt::Display for DistributionTable {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let row = |f: &mut fmt::Formatter<'_>, label: &str, values: &[[f64; 2]]| -> fmt::Result {
+            write!(f, "{:<8}", label)?;
+            for value in values.iter().flatten() {
+                write!(f, "{:>12.4}", value)?;
+            }
+            writeln!(f)
+        };
+
+        write!(f, "{:<8}", "Span")?;
+        for i in 0..self.final_moments.len() {
+            write!(f, "{:>12}{:>12}", format!("{}A", i), format!("{}B", i))?;
+        }
+        writeln!(f)?;
+        row(f, "DF", &self.distribution_factors)?;
+        for distribution_row in &self.rows {
+            let label = match distribution_row.step {
+                Step::FixedEnd => "FEM".to_string(),
+                Step::Balance(cycle) => format!("Bal {}", cycle),
+                Step::CarryOver(cycle) => format!("CO {}", cycle),
+            };
+            row(f, &label, &distribution_row.moments)?;
+        }
+        row(f, "Final", &self.final_moments)
+    }
+}
+
+#[cfg(test)]
+mod moment_distribution_tests {
+    use super::*;
+    use crate::{
+        loads::{distributed_load::DistributedLoad, point_load::PunctualLoad},
+        members::traits::{BeamBuilder, BeamTraits},
+        spans::traits::SpanBuilder,
+        test_support::assert_close_to,
+    };
+
+    // Compares the distribution with the slope deflection solution of the same beam
+    fn assert_matches_slope_deflection(mut beam: ContinuousBeam) -> DistributionTable {
+        let table = MomentDistribution::new(&beam).with_tolerance(1e-9).distribute().unwrap();
+        beam.analyze().unwrap();
+        for (expected, actual) in beam.get_end_moments().iter().zip(&table.final_moments) {
+            assert_close_to(expected[0], actual[0], 1e-6);
+            assert_close_to(expected[1], actual[1], 1e-6);
+        }
+        table
+    }
+
+    #[test]
+    fn test_unequal_spans_with_fixed_end() {
+        let a = Node::new(0.0, 0.0, SupportType::Fixed);
+        let b = Node::new(6.0, 0.0, SupportType::Roller);
+        let c = Node::new(14.0, 0.0, SupportType::Hinged);
+        let ab = Span::new(a, b, vec![DistributedLoad::uniform(10.0, 0.0, 6.0).unwrap()], vec![]);
+        let bc = Span::new(b, c, vec![], vec![PunctualLoad { value: 40.0, x: 4.0 }]).with_ei(2.0);
+
+        let table = assert_matches_slope_deflection(ContinuousBeam::new(vec![ab, bc]));
+
+        // 4EI/L of 2/3 and, with C pinned, 3EI/L of 3/4 at B, so the factors there are 8/17 and 9/17
+        assert_close_to(8.0 / 17.0, table.distribution_factors[0][1], 1e-12);
+        assert_close_to(9.0 / 17.0, table.distribution_factors[1][0], 1e-12);
+        assert_eq!(0.0, table.distribution_factors[0][0]);
+        assert_eq!(0.0, table.distribution_factors[1][1]);
+        assert_eq!([0.75, 0.0], table.stiffness[1]);
+        assert_eq!(Step::FixedEnd, table.rows[0].step);
+        assert_eq!([-30.0, 30.0], table.rows[0].moments[0]);
+        // Propped fixed end moment -3PL/16 with C pinned
+        assert_eq!([-60.0, 0.0], table.rows[0].moments[1]);
+        assert_eq!(1 + 2 * table.cycles, table.rows.len());
+    }
+
+    #[test]
+    fn test_settlement_overhang_and_spring() {
+        let a = Node::new(0.0, 0.0, SupportType::Spring { translational: f64::INFINITY, rotational: 500.0 });
+        let b = Node::new(5.0, 0.01, SupportType::Roller);
+        let c = Node::new(10.0, 0.0, SupportType::Roller);
+        let d = Node::new(12.0, 0.0, SupportType::Free);
+        let ab = Span::new(a, b, vec![DistributedLoad::uniform(8.0, 0.0, 5.0).unwrap()], vec![]).with_ei(1000.0);
+        let bc = Span::new(b, c, vec![], vec![]).with_ei(1000.0);
+        let cd = Span::new(c, d, vec![], vec![PunctualLoad { value: 15.0, x: 2.0 }]).with_ei(1000.0);
+
+        let table = assert_matches_slope_deflection(ContinuousBeam::new(vec![ab, bc, cd]).with_joint_moment(2, 5.0));
+        assert_eq!([0.0, 0.0], table.stiffness[2]);
+        assert_eq!([-30.0, 0.0], table.rows[0].moments[2]);
+    }
+
+    #[test]
+    fn test_couples_at_the_ends_of_the_beam() {
+        // A couple at a free tip stays in the overhang and reaches its support undiminished
+        let a = Node::new(0.0, 0.0, SupportType::Free);
+        let b = Node::new(2.0, 0.0, SupportType::Roller);
+        let c = Node::new(8.0, 0.0, SupportType::Hinged);
+        let ab = Span::new(a, b, vec![], vec![PunctualLoad { value: 10.0, x: 0.0 }]);
+        let bc = Span::new(b, c, vec![], vec![]);
+        let table = assert_matches_slope_deflection(ContinuousBeam::new(vec![ab, bc]).with_joint_moment(0, 3.0));
+        assert_eq!(vec![[3.0, 17.0], [-17.0, 0.0]], table.final_moments);
+
+        let a = Node::new(0.0, 0.0, SupportType::Fixed);
+        let b = Node::new(6.0, 0.0, SupportType::Roller);
+        let c = Node::new(8.0, 0.0, SupportType::Free);
+        let beam = ContinuousBeam::new(vec![Span::new(a, b, vec![], vec![]), Span::new(b, c, vec![], vec![])]);
+        let table = assert_matches_slope_deflection(beam.with_joint_moment(2, 10.0));
+        assert_close_to(5.0, table.final_moments[0][0], 1e-6);
+        assert_close_to(10.0, table.final_moments[1][1], 1e-6);
+
+        // A couple at a pinned end carries half over to the held end beyond it
+        let a = Node::new(0.0, 0.0, SupportType::Hinged);
+        let b = Node::new(6.0, 0.0, SupportType::Roller);
+        let c = Node::new(12.0, 0.0, SupportType::Fixed);
+        let beam = ContinuousBeam::new(vec![Span::new(a, b, vec![], vec![]), Span::new(b, c, vec![], vec![])]);
+        let table = assert_matches_slope_deflection(beam.with_joint_moment(0, 12.0));
+        assert_eq!([12.0, 6.0], table.rows[0].moments[0]);
+    }
+
+    #[test]
+    fn test_settlement_next_to_pinned_end() {
+        let a = Node::new(0.0, 0.0, SupportType::Hinged);
+        let b = Node::new(5.0, 0.01, SupportType::Roller);
+        let c = Node::new(11.0, 0.0, SupportType::Roller);
+        let ab = Span::new(a, b, vec![], vec![PunctualLoad { value: 20.0, x: 2.0 }]).with_ei(1000.0);
+        let bc = Span::new(b, c, vec![DistributedLoad::uniform(6.0, 0.0, 6.0).unwrap()], vec![]).with_ei(1500.0);
+
+        let table = assert_matches_slope_deflection(ContinuousBeam::new(vec![ab, bc]));
+        // Only B is balanced, so one cycle settles it
+        assert_eq!(1, table.cycles);
+        assert_eq!([0.0, 0.0], table.rows[2].moments[0]);
+    }
+
+    #[test]
+    fn test_table_layout() {
+        let a = Node::new(0.0, 0.0, SupportType::Fixed);
+        let b = Node::new(4.0, 0.0, SupportType::Roller);
+        let span = Span::new(a, b, vec![DistributedLoad::uniform(12.0, 0.0, 4.0).unwrap()], vec![]);
+        let table = MomentDistribution::new(&ContinuousBeam::new(vec![span])).distribute().unwrap();
+
+        // With B pinned the propped fixed end moment -wL²/8 needs no distribution
+        assert_eq!(0, table.cycles);
+        assert_eq!([-24.0, 0.0], table.final_moments[0]);
+        let text = table.to_string();
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(4, lines.len());
+        assert!(lines[0].contains("0A") && lines[0].contains("0B"));
+        assert!(lines[2].starts_with("FEM") && lines[2].contains("-24.0000"));
+        assert!(lines[3].starts_with("Final") && lines[3].contains("-24.0000"));
+    }
+
+    #[test]
+    fn test_rejects_what_it_cannot_distribute() {
+        let a = Node::new(0.0, 0.0, SupportType::Fixed);
+        let b = Node::new(4.0, 0.0, SupportType::Guided);
+        let span = Span::new(a, b, vec![], vec![]);
+        let distribution = MomentDistribution::new(&ContinuousBeam::new(vec![span]));
+        assert_eq!(Err(AnalysisError::UnsupportedNode(1)), distribution.distribute().map(|table| table.cycles));
+
+        let a = Node::new(0.0, 0.0, SupportType::Fixed);
+        let b = Node::new(4.0, 0.0, SupportType::Roller);
+        let c = Node::new(8.0, 0.0, SupportType::Roller);
+        let d = Node::new(12.0, 0.0, SupportType::Fixed);
+        let spans = vec![
+            Span::new(a, b, vec![DistributedLoad::uniform(10.0, 0.0, 4.0).unwrap()], vec![]),
+            Span::new(b, c, vec![], vec![]),
+            Span::new(c, d, vec![], vec![]),
+        ];
+        let distribution = MomentDistribution::new(&ContinuousBeam::new(spans)).with_tolerance(1e-12).with_max_cycles(3);
+        assert_eq!(Err(AnalysisError::NotConverged(3)), distribution.distribute().map(|table| table.cycles));
+    }
+}
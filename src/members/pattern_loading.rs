@@ -0,0 +1,280 @@
+use crate::{
+    loads::{load_case::LoadCase, load_combination::{DesignCode, LoadCombination}},
+    nodes::node::NodeTraits,
+    spans::traits::{SpanDetails, SpanTraits},
+};
+use super::{beam::ContinuousBeam, error::AnalysisError, model::BeamModel, traits::{BeamDetails, BeamTraits}};
+
+// Spans carrying live load in each arrangement: every span, alternate spans, and the two spans
+// either side of each interior support with alternate spans beyond them
+pub fn arrangements(spans: usize) -> Vec<Vec<bool>> {
+    if spans == 0 {
+        return vec![];
+    }
+    let mut patterns = vec![vec![true; spans]];
+    patterns.push((0..spans).map(|i| i % 2 == 0).collect());
+    patterns.push((0..spans).map(|i| i % 2 == 1).collect());
+    for support in 1..spans {
+        patterns.push((0..spans).map(|i| match i {
+            i if i < support => (support - 1 - i) % 2 == 0,
+            i => (i - support) % 2 == 0,
+        }).collect());
+    }
+
+    let mut unique: Vec<Vec<bool>> = vec![];
+    for pattern in patterns {
+        if pattern.iter().any(|&loaded| loaded) && !unique.contains(&pattern) {
+            unique.push(pattern);
+        }
+    }
+    unique
+}
+
+// Extreme shear and moment at a station over every arrangement. Where a load or a support makes
+// the diagram jump, the station appears once for each side
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EnvelopePoint {
+    pub x: f64,
+    pub max_shear: f64,
+    pub min_shear: f64,
+    pub max_moment: f64,
+    pub min_moment: f64,
+}
+
+impl EnvelopePoint {
+    // Extremes of the (shear, moment) pairs found at x
+    fn of(x: f64, values: impl Iterator<Item = (f64, f64)>) -> Self {
+        let start = EnvelopePoint {
+            x,
+            max_shear: f64::NEG_INFINITY,
+            min_shear: f64::INFINITY,
+            max_moment: f64::NEG_INFINITY,
+            min_moment: f64::INFINITY,
+        };
+        values.fold(start, |point, (shear, moment)| EnvelopePoint {
+            max_shear: point.max_shear.max(shear),
+            min_shear: point.min_shear.min(shear),
+            max_moment: point.max_moment.max(moment),
+            min_moment: point.min_moment.min(moment),
+            ..point
+        })
+    }
+}
+
+// Extreme support reactions at a node over every arrangement
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ReactionEnvelope {
+    pub node: usize,
+    pub x: f64,
+    pub max_force: f64,
+    pub min_force: f64,
+    pub max_moment: f64,
+    pub min_moment: f64,
+}
+
+#[derive(Debug, Clone)]
+pub struct Envelope {
+    pub arrangements: Vec<Vec<bool>>,
+    pub points: Vec<EnvelopePoint>,
+    pub reactions: Vec<ReactionEnvelope>,
+}
+
+// Continuous beam under dead load on every span and live load on the spans of each arrangement,
+// a span cut by a hinge loaded as one. The load cases are split span by span and solved once
+// against the same factored stiffness matrix, and every arrangement is superposed from those
+// solutions
+#[derive(Debug, Clone)]
+pub struct PatternLoading {
+    beam: ContinuousBeam,
+    dead: LoadCase,
+    live: LoadCase,
+    // Factors on dead load over loaded and unloaded spans, and on live load
+    factors: [f64; 3],
+    arrangements: Vec<Vec<bool>>,
+    results: Vec<ContinuousBeam>,
+}
+
+impl PatternLoading {
+    pub fn new(beam: ContinuousBeam, dead: LoadCase, live: LoadCase) -> Self {
+        let arrangements = arrangements(beam.built_spans());
+        PatternLoading {
+            beam: beam.unloaded(),
+            dead,
+            live,
+            factors: [1.0; 3],
+            arrangements,
+            results: vec![],
+        }
+    }
+
+    pub fn with_factors(mut self, dead_adverse: f64, dead_beneficial: f64, live: f64) -> Self {
+        self.factors = [dead_adverse, dead_beneficial, live];
+        self
+    }
+
+    pub fn with_design_code(mut self, code: DesignCode) -> Self {
+        self.factors = code.pattern_factors();
+        self
+    }
+
+    pub fn get_arrangements(&self) -> &[Vec<bool>] {
+        &self.arrangements
+    }
+
+    fn combination(&self, k: usize) -> LoadCombination {
+        let [dead_adverse, dead_beneficial, live] = self.factors;
+        let combination = LoadCombination::new(&format!("Pattern {}", k + 1))
+            .with_factor("G joints", dead_adverse)
+            .with_factor("Q joints", live);
+        self.arrangements[k].iter().enumerate().fold(combination, |combination, (i, &loaded)| {
+            match loaded {
+                true => combination.with_factor(&format!("G{i}"), dead_adverse).with_factor(&format!("Q{i}"), live),
+                false => combination.with_factor(&format!("G{i}"), dead_beneficial),
+            }
+        })
+    }
+
+    pub fn analyze(&mut self) -> Result<(), AnalysisError> {
+        let spans = self.beam.built_spans();
+        let combinations = (0..self.arrangements.len()).map(|k| self.combination(k)).collect();
+        let mut model = (0..spans)
+            .fold(BeamModel::new(self.beam.clone()), |model, i| {
+                model
+                    .with_load_case(self.dead.on_span(i, &format!("G{i}")))
+                    .with_load_case(self.live.on_span(i, &format!("Q{i}")))
+            })
+            .with_load_case(self.dead.at_joints("G joints"))
+            .with_load_case(self.live.at_joints("Q joints"))
+            .with_combinations(combinations);
+        model.analyze()?;
+        self.results = model.get_combination_results().to_vec();
+        Ok(())
+    }
+
+    // Analysed beam of every arrangement, in the order of `get_arrangements`
+    pub fn get_results(&self) -> &[ContinuousBeam] {
+        &self.results
+    }
+
+    // Envelope at `points` evenly spaced stations per span plus every concentrated load position
+    pub fn envelope(&self, points: usize) -> Envelope {
+        let mut envelope = Envelope { arrangements: self.arrangements.clone(), points: vec![], reactions: vec![] };
+        let Some(first) = self.results.first() else {
+            return envelope;
+        };
+
+        for (s, span) in first.get_spans().iter().enumerate() {
+            let offset = span.get_start_node().get_x();
+            // The first arrangement loads every span, so its diagram has every station
+            let mut stations: Vec<f64> = span.diagram(points).iter().map(|point| point.x).collect();
+            stations.dedup_by(|a, b| (*a - *b).abs() < 1e-12);
+
+            for x in stations {
+                let spans = || self.results.iter().map(|beam| &beam.get_spans()[s]);
+                let before = EnvelopePoint::of(x + offset, spans().map(|span| (span.shear_before(x), span.moment_before(x))));
+                let after = EnvelopePoint::of(x + offset, spans().map(|span| (span.shear_at(x), span.moment_at(x))));
+                let jump = [
+                    before.max_shear - after.max_shear,
+                    before.min_shear - after.min_shear,
+                    before.max_moment - after.max_moment,
+                    before.min_moment - after.min_moment,
+                ];
+                if jump.iter().any(|difference| difference.abs() > 1e-12) {
+                    envelope.points.push(before);
+                }
+                envelope.points.push(after);
+            }
+        }
+
+        let reactions: Vec<_> = self.results.iter().map(|beam| beam.get_reactions()).collect();
+        envelope.reactions = first.get_reactions().iter().map(|reaction| ReactionEnvelope {
+            node: reaction.node,
+            x: reaction.x,
+            max_force: reactions.iter().map(|all| all[reaction.node].force).fold(f64::NEG_INFINITY, f64::max),
+            min_force: reactions.iter().map(|all| all[reaction.node].force).fold(f64::INFINITY, f64::min),
+            max_moment: reactions.iter().map(|all| all[reaction.node].moment).fold(f64::NEG_INFINITY, f64::max),
+            min_moment: reactions.iter().map(|all| all[reaction.node].moment).fold(f64::INFINITY, f64::min),
+        }).collect();
+        envelope
+    }
+}
+
+#[cfg(test)]
+mod pattern_loading_tests {
+    use super::*;
+    use crate::{
+        loads::{distributed_load::DistributedLoad, load_case::LoadCaseKind},
+        members::traits::BeamBuilder,
+        nodes::node::{Node, SupportType},
+        spans::{span::Span, traits::SpanBuilder},
+        test_support::assert_close,
+    };
+
+    // Three equal 6 m spans on simple supports
+    fn three_spans() -> ContinuousBeam {
+        let nodes = [
+            Node::new(0.0, 0.0, SupportType::Hinged),
+            Node::new(6.0, 0.0, SupportType::Roller),
+            Node::new(12.0, 0.0, SupportType::Roller),
+            Node::new(18.0, 0.0, SupportType::Roller),
+        ];
+        ContinuousBeam::new(nodes.windows(2).map(|pair| Span::new(pair[0], pair[1], vec![], vec![])).collect())
+    }
+
+    fn uniform(name: &str, kind: LoadCaseKind, value: f64) -> LoadCase {
+        (0..3).fold(LoadCase::new(name, kind), |case, i| {
+            case.with_distributed_load(i, DistributedLoad::uniform(value, 0.0, 6.0).unwrap())
+        })
+    }
+
+    fn at(envelope: &Envelope, x: f64) -> Vec<EnvelopePoint> {
+        envelope.points.iter().filter(|point| (point.x - x).abs() < 1e-9).copied().collect()
+    }
+
+    #[test]
+    fn test_arrangements() {
+        let patterns = arrangements(3);
+        assert_eq!(vec![
+            vec![true, true, true],
+            vec![true, false, true],
+            vec![false, true, false],
+            vec![true, true, false],
+            vec![false, true, true],
+        ], patterns);
+        assert_eq!(vec![vec![true]], arrangements(1));
+        assert_eq!(3, arrangements(2).len());
+    }
+
+    #[test]
+    fn test_live_load_envelope() {
+        let mut patterns = PatternLoading::new(three_spans(), LoadCase::new("Dead", LoadCaseKind::Dead), uniform("Live", LoadCaseKind::Live, 10.0));
+        patterns.analyze().unwrap();
+        assert_eq!(5, patterns.get_results().len());
+        let envelope = patterns.envelope(5);
+
+        // Worst hogging at B, 7wL²/60, with the two spans either side loaded
+        let support = at(&envelope, 6.0);
+        assert_eq!(2, support.len());
+        assert_close(-42.0, support[0].min_moment);
+        // Worst sagging in the end span with the end spans loaded, wL²/8 - wL²/40
+        assert_close(36.0, at(&envelope, 3.0)[0].max_moment);
+        // Shear either side of B with the two spans either side loaded, where M_C = -wL²/30
+        assert_close(-37.0, support[0].min_shear);
+        assert_close(35.0, support[1].max_shear);
+
+        // Uplift at A with only the middle span loaded
+        let a = envelope.reactions[0];
+        assert_close(27.0, a.max_force);
+        assert_close(-3.0, a.min_force);
+    }
+
+    #[test]
+    fn test_design_code_factors() {
+        let mut patterns = PatternLoading::new(three_spans(), uniform("Dead", LoadCaseKind::Dead, 10.0), LoadCase::new("Live", LoadCaseKind::Live))
+            .with_design_code(DesignCode::Bs8110);
+        patterns.analyze().unwrap();
+
+        // 1.4 on the two spans either side of B and 1.0 on the third
+        assert_close(-52.8, at(&patterns.envelope(5), 6.0)[0].min_moment);
+    }
+}
@@ -0,0 +1,29 @@
+// Support reaction at a node, acting on the member
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Reaction {
+    pub node: usize,
+    pub x: f64,
+    // Vertical force, upward positive
+    pub force: f64,
+    // Reaction moment, clockwise positive, zero where the rotation is free
+    pub moment: f64,
+}
+
+// Residuals of the overall equilibrium of a member under its loads and reactions
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EquilibriumCheck {
+    // Total downward applied load
+    pub applied_force: f64,
+    // Total upward reaction force
+    pub reaction_force: f64,
+    // Applied load minus reactions
+    pub force_residual: f64,
+    // Sum of moments about the first node, clockwise positive
+    pub moment_residual: f64,
+}
+
+impl EquilibriumCheck {
+    pub fn is_satisfied(&self, tolerance: f64) -> bool {
+        self.force_residual.abs() <= tolerance && self.moment_residual.abs() <= tolerance
+    }
+}
@@ -0,0 +1,105 @@
+use crate::{loads::load_case::LoadCase, nodes::node::Node, spans::{diagram::{DeflectionPoint, DiagramPoint}, span::Span}};
+use super::{error::AnalysisError, reaction::{EquilibriumCheck, Reaction}};
+
+pub trait BeamBuilder {
+    fn new(spans: Vec<Span>) -> Self;
+
+    // Concentrated couple applied directly at a node, clockwise positive
+    fn with_joint_moment(self, node: usize, moment: f64) -> Self;
+
+    // Imposed settlement of a supported node, downward positive
+    fn with_settlement(self, node: usize, settlement: f64) -> Self;
+
+    // Adds the loads, couples and settlements of a load case to the beam. Its spans and nodes are
+    // those of the beam as built, a span cut by a hinge keeps its index and its loads are shared
+    // out between the two parts. The analysis rejects a case that does not fit the beam
+    fn with_load_case(self, case: &LoadCase) -> Self;
+
+    // Internal hinge at x measured from the first node, releasing the moment at an existing node
+    // or splitting the span there with a free node. The analysis rejects a hinge at either end of
+    // the beam or beyond it
+    fn with_hinge_at(self, x: f64) -> Self;
+}
+
+pub trait BeamDetails {
+    fn get_nodes(&self) -> &[Node];
+    fn get_spans(&self) -> &[Span];
+    fn get_joint_moments(&self) -> &[f64];
+}
+
+pub trait BeamTraits {
+    // Solves the joint equilibrium equations for the unknown rotations and translations
+    fn analyze(&mut self) -> Result<(), AnalysisError>;
+
+    // Rotation of every node, clockwise positive
+    fn get_rotations(&self) -> Vec<f64>;
+
+    // Settlement or solved deflection of every node, downward positive
+    fn get_deflections(&self) -> Vec<f64>;
+
+    // Final Member End Moments of every span, clockwise positive
+    fn get_end_moments(&self) -> Vec<[f64; 2]>;
+
+    // Final Member End Shears of every span, upward positive
+    fn get_end_shears(&self) -> Vec<[f64; 2]>;
+
+    // Support reaction at every node, assembled from the adjacent span ends
+    fn get_reactions(&self) -> Vec<Reaction>;
+
+    // Overall vertical and moment equilibrium of the reactions against the applied loads
+    fn check_equilibrium(&self) -> EquilibriumCheck;
+
+    // Shear and moment at stations measured from the first node
+    fn diagram_at(&self, stations: &[f64]) -> Vec<DiagramPoint>;
+
+    // Shear and moment at `points` stations per span, including both sides of every support
+    fn get_diagram(&self, points: usize) -> Vec<DiagramPoint>;
+
+    // Slope and deflection at `points` stations per span, measured from the first node
+    fn get_deflection_curve(&self, points: usize) -> Vec<DeflectionPoint>;
+
+    // Location and value of the largest deflection of every span, measured from the first node
+    fn get_max_deflections(&self) -> Vec<DeflectionPoint>;
+}
+
+pub trait FrameBuilder {
+    // Members meeting at coincident end nodes share a joint, the first member to reach a joint sets its support
+    fn new(members: Vec<Span>) -> Self;
+
+    // Concentrated couple applied directly at a joint, clockwise positive
+    fn with_joint_moment(self, node: usize, moment: f64) -> Self;
+
+    // Concentrated force applied directly at a joint, rightward and downward positive
+    fn with_joint_load(self, node: usize, horizontal: f64, vertical: f64) -> Self;
+}
+
+pub trait FrameDetails {
+    fn get_nodes(&self) -> &[Node];
+    fn get_members(&self) -> &[Span];
+
+    // Start and end joint of every member
+    fn get_connectivity(&self) -> &[[usize; 2]];
+    fn get_joint_moments(&self) -> &[f64];
+    fn get_joint_loads(&self) -> &[[f64; 2]];
+}
+
+pub trait FrameTraits {
+    // Solves the joint and sway mechanism equilibrium equations for the unknown joint rotations,
+    // sways and deflections
+    fn analyze(&mut self) -> Result<(), AnalysisError>;
+
+    // Rotation of every joint, clockwise positive
+    fn get_rotations(&self) -> Vec<f64>;
+
+    // Sideways displacement of every joint, rightward positive
+    fn get_sways(&self) -> Vec<f64>;
+
+    // Settlement or solved deflection of every joint, downward positive
+    fn get_deflections(&self) -> Vec<f64>;
+
+    // Final Member End Moments of every member, clockwise positive
+    fn get_end_moments(&self) -> Vec<[f64; 2]>;
+
+    // Final Member End Shears of every member, square to it and against its loads
+    fn get_end_shears(&self) -> Vec<[f64; 2]>;
+}
@@ -1,34 +1,123 @@
 #[derive(Debug, Clone, Copy)]
 pub struct Node {
     x: f64,
+    y: f64,
     settlement: f64,
-    support: SupportType
+    rotation: f64,
+    support: SupportType,
+    translation_dof: DofState,
+    rotation_dof: DofState,
+    moment_release: bool,
+    rotation_right: f64,
 }
 
 #[derive(Debug, Clone, Copy)]
 pub enum SupportType {
     Fixed,
     Hinged,
-    Roller
+    Roller,
+    // Unsupported end of a cantilever or overhang
+    Free,
+    // Sliding support restraining rotation only, it carries a moment but no shear
+    Guided,
+    // Elastic support, force per unit settlement and moment per unit rotation. An infinite
+    // stiffness restrains the displacement, a zero one leaves it free
+    Spring { translational: f64, rotational: f64 },
+}
+
+// Whether a degree of freedom is imposed by the support or solved by the analysis
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DofState {
+    Prescribed,
+    Unknown,
+}
+
+impl SupportType {
+    // Translation and rotation states imposed by the support
+    pub fn dofs(&self) -> [DofState; 2] {
+        match self {
+            SupportType::Fixed => [DofState::Prescribed, DofState::Prescribed],
+            SupportType::Hinged | SupportType::Roller => [DofState::Prescribed, DofState::Unknown],
+            SupportType::Free => [DofState::Unknown, DofState::Unknown],
+            SupportType::Guided => [DofState::Unknown, DofState::Prescribed],
+            SupportType::Spring { translational, rotational } => {
+                let state = |stiffness: f64| if stiffness.is_infinite() { DofState::Prescribed } else { DofState::Unknown };
+                [state(*translational), state(*rotational)]
+            }
+        }
+    }
+
+    // Translational and rotational stiffness tying an unknown displacement to its reaction
+    pub fn stiffness(&self) -> [f64; 2] {
+        match self {
+            SupportType::Spring { translational, rotational } => [*translational, *rotational],
+            _ => [0.0, 0.0],
+        }
+    }
 }
 
 pub trait NodeTraits {
     fn new(x: f64, settlement: f64, support: SupportType) -> Self;
     fn get_x(&self) -> f64;
+    fn get_y(&self) -> f64;
+
+    // Places the node at height y, for the joints of a frame
+    fn with_y(self, y: f64) -> Self;
+
+    // Joint of a frame at (x, y), with no settlement
+    fn at(x: f64, y: f64, support: SupportType) -> Self where Self: Sized {
+        Self::new(x, 0.0, support).with_y(y)
+    }
+
     fn get_support(&self) -> SupportType;
     fn get_deflection(&self) -> f64;
     fn get_rotation(&self) -> f64;
+    fn get_translation_dof(&self) -> DofState;
+    fn get_rotation_dof(&self) -> DofState;
+
+    // Write back a solved settlement, downward positive
+    fn set_deflection(&mut self, deflection: f64);
+
+    // Write back a solved rotation, clockwise positive
+    fn set_rotation(&mut self, rotation: f64);
+
+    // Internal hinge: the members left and right of the node rotate independently and carry no moment
+    fn with_moment_release(self) -> Self;
+    fn has_moment_release(&self) -> bool;
+
+    // Rotation of the member end right of the node, differs from `get_rotation` across a hinge
+    fn get_rotation_right(&self) -> f64;
+    fn set_rotation_right(&mut self, rotation: f64);
 }
 
 impl NodeTraits for Node {
     fn new(x: f64, settlement: f64, support: SupportType) -> Self {
-        Node { x, settlement, support }
+        let [translation_dof, rotation_dof] = support.dofs();
+        Node {
+            x,
+            y: 0.0,
+            settlement,
+            rotation: 0.0,
+            support,
+            translation_dof,
+            rotation_dof,
+            moment_release: false,
+            rotation_right: 0.0,
+        }
     }
 
     fn get_x(&self) -> f64 {
         self.x
     }
 
+    fn get_y(&self) -> f64 {
+        self.y
+    }
+
+    fn with_y(self, y: f64) -> Self {
+        Node { y, ..self }
+    }
+
     fn get_support(&self) -> SupportType {
         self.support
     }
@@ -37,8 +126,104 @@ impl NodeTraits for Node {
         self.settlement
     }
 
-    //TODO: Find a way to set rotation
     fn get_rotation(&self) -> f64 {
-        0.0
+        self.rotation
+    }
+
+    fn get_translation_dof(&self) -> DofState {
+        self.translation_dof
+    }
+
+    fn get_rotation_dof(&self) -> DofState {
+        self.rotation_dof
+    }
+
+    fn set_deflection(&mut self, deflection: f64) {
+        self.settlement = deflection;
+    }
+
+    fn set_rotation(&mut self, rotation: f64) {
+        self.rotation = rotation;
+    }
+
+    fn with_moment_release(self) -> Self {
+        Node { moment_release: true, ..self }
+    }
+
+    fn has_moment_release(&self) -> bool {
+        self.moment_release
+    }
+
+    fn get_rotation_right(&self) -> f64 {
+        if self.moment_release { self.rotation_right } else { self.rotation }
+    }
+
+    fn set_rotation_right(&mut self, rotation: f64) {
+        self.rotation_right = rotation;
+    }
+}
+
+#[cfg(test)]
+mod node_tests {
+    use super::*;
+
+    #[test]
+    fn test_dofs_follow_support_type() {
+        let fixed = Node::new(0.0, 0.0, SupportType::Fixed);
+        assert_eq!(DofState::Prescribed, fixed.get_translation_dof());
+        assert_eq!(DofState::Prescribed, fixed.get_rotation_dof());
+
+        let roller = Node::new(4.0, 0.0, SupportType::Roller);
+        assert_eq!(DofState::Prescribed, roller.get_translation_dof());
+        assert_eq!(DofState::Unknown, roller.get_rotation_dof());
+
+        let free = Node::new(6.0, 0.0, SupportType::Free);
+        assert_eq!(DofState::Unknown, free.get_translation_dof());
+        assert_eq!(DofState::Unknown, free.get_rotation_dof());
+
+        let guided = Node::new(6.0, 0.0, SupportType::Guided);
+        assert_eq!(DofState::Unknown, guided.get_translation_dof());
+        assert_eq!(DofState::Prescribed, guided.get_rotation_dof());
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_spring_dofs_follow_stiffness() {
+        let spring = Node::new(0.0, 0.0, SupportType::Spring { translational: 5000.0, rotational: 0.0 });
+        assert_eq!(DofState::Unknown, spring.get_translation_dof());
+        assert_eq!(DofState::Unknown, spring.get_rotation_dof());
+        assert_eq!([5000.0, 0.0], spring.get_support().stiffness());
+
+        let clamped = Node::new(0.0, 0.0, SupportType::Spring { translational: 5000.0, rotational: f64::INFINITY });
+        assert_eq!(DofState::Unknown, clamped.get_translation_dof());
+        assert_eq!(DofState::Prescribed, clamped.get_rotation_dof());
+        assert_eq!([0.0, 0.0], SupportType::Fixed.stiffness());
+    }
+
+    #[test]
+    fn test_coordinates_are_separate_from_settlement() {
+        let mut node = Node::at(3.0, 4.0, SupportType::Free);
+        node.set_deflection(0.01);
+        assert_eq!(3.0, node.get_x());
+        assert_eq!(4.0, node.get_y());
+        assert_eq!(0.01, node.get_deflection());
+    }
+
+    #[test]
+    fn test_set_rotation() {
+        let mut node = Node::new(0.0, 0.0, SupportType::Hinged);
+        assert_eq!(0.0, node.get_rotation());
+        node.set_rotation(0.25);
+        assert_eq!(0.25, node.get_rotation());
+        assert_eq!(0.25, node.get_rotation_right());
+    }
+
+    #[test]
+    fn test_moment_release_rotates_sides_independently() {
+        let mut node = Node::new(4.0, 0.0, SupportType::Free).with_moment_release();
+        node.set_rotation(0.25);
+        node.set_rotation_right(-0.5);
+        assert!(node.has_moment_release());
+        assert_eq!(0.25, node.get_rotation());
+        assert_eq!(-0.5, node.get_rotation_right());
+    }
+}
@@ -0,0 +1,97 @@
+use super::error::{check_dimensions, check_pivot, scale, SolverError};
+
+// Cholesky factorization A = LLᵀ of a symmetric positive definite matrix, such as the stiffness
+// matrix of a stable structure
+#[derive(Debug, Clone)]
+pub struct Cholesky {
+    lower: Vec<Vec<f64>>,
+}
+
+impl Cholesky {
+    pub fn factorize(matrix: Vec<Vec<f64>>) -> Result<Self, SolverError> {
+        let n = matrix.len();
+        check_dimensions(&matrix, n)?;
+        let scale = scale(&matrix);
+        let symmetric = matrix.iter().enumerate().all(|(i, row)| {
+            row[..i].iter().enumerate().all(|(j, value)| (value - matrix[j][i]).abs() <= 1e-12 * scale)
+        });
+        if !symmetric {
+            return Err(SolverError::NotSymmetric);
+        }
+
+        let mut lower = vec![vec![0.0; n]; n];
+        for j in 0..n {
+            let diagonal = matrix[j][j] - (0..j).map(|k| lower[j][k] * lower[j][k]).sum::<f64>();
+            if diagonal <= 0.0 {
+                return Err(SolverError::NotPositiveDefinite(j));
+            }
+            check_pivot(diagonal, matrix[j][j], n, j)?;
+            lower[j][j] = diagonal.sqrt();
+            for i in j + 1..n {
+                let sum = matrix[i][j] - (0..j).map(|k| lower[i][k] * lower[j][k]).sum::<f64>();
+                lower[i][j] = sum / lower[j][j];
+            }
+        }
+        Ok(Cholesky { lower })
+    }
+
+    pub fn solve(&self, rhs: &[f64]) -> Result<Vec<f64>, SolverError> {
+        let n = self.lower.len();
+        if rhs.len() != n {
+            return Err(SolverError::DimensionMismatch);
+        }
+
+        // Forward substitution through L, then back substitution through Lᵀ
+        let mut x = rhs.to_vec();
+        for row in 0..n {
+            let sum = (0..row).fold(x[row], |accum, k| accum - self.lower[row][k] * x[k]);
+            x[row] = sum / self.lower[row][row];
+        }
+        for row in (0..n).rev() {
+            let sum = (row + 1..n).fold(x[row], |accum, k| accum - self.lower[k][row] * x[k]);
+            x[row] = sum / self.lower[row][row];
+        }
+        Ok(x)
+    }
+}
+
+// Solves `matrix * x = rhs` for a symmetric positive definite matrix
+pub fn solve(matrix: Vec<Vec<f64>>, rhs: Vec<f64>) -> Result<Vec<f64>, SolverError> {
+    check_dimensions(&matrix, rhs.len())?;
+    Cholesky::factorize(matrix)?.solve(&rhs)
+}
+
+#[cfg(test)]
+mod cholesky_tests {
+    use super::*;
+
+    #[test]
+    fn test_solve_stiffness_matrix() {
+        // Two springs in series, k = 2 then k = 1, pulled at the free end
+        let matrix = vec![vec![3.0, -1.0], vec![-1.0, 1.0]];
+        let x = solve(matrix, vec![0.0, 2.0]).unwrap();
+        assert!((x[0] - 1.0).abs() < 1e-12);
+        assert!((x[1] - 3.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_rejects_unsuitable_matrices() {
+        let matrix = vec![vec![1.0, 2.0], vec![0.0, 1.0]];
+        assert_eq!(Err(SolverError::NotSymmetric), solve(matrix, vec![1.0, 1.0]));
+        let matrix = vec![vec![1.0, 2.0], vec![2.0, 1.0]];
+        assert_eq!(Err(SolverError::NotPositiveDefinite(1)), solve(matrix, vec![1.0, 1.0]));
+        let matrix = vec![vec![1.0, 1.0], vec![1.0, 1.0]];
+        assert_eq!(Err(SolverError::NotPositiveDefinite(1)), solve(matrix, vec![1.0, 1.0]));
+        let matrix = vec![vec![1.0, 1.0], vec![1.0, 1.0 + 1e-12]];
+        assert_eq!(Err(SolverError::IllConditioned(1)), solve(matrix, vec![1.0, 2.0]));
+    }
+
+    #[test]
+    fn test_stiff_spring_beside_a_soft_one() {
+        // Springs of 1e12 and 1 in series, the soft one pulled at its free end
+        let matrix = vec![vec![1e12 + 1.0, -1.0], vec![-1.0, 1.0]];
+        let x = solve(matrix, vec![0.0, 1.0]).unwrap();
+        assert!((x[0] - 1e-12).abs() < 1e-24);
+        assert!((x[1] - 1.0 - 1e-12).abs() < 1e-12);
+    }
+}
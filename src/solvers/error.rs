@@ -0,0 +1,54 @@
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SolverError {
+    // The matrix is not square or does not match the length of the right hand side
+    DimensionMismatch,
+    // The pivot in column `i` vanishes, the equations are not independent
+    Singular(usize),
+    // The pivot in column `i` is so small next to its own row that the solution is meaningless
+    IllConditioned(usize),
+    // The matrix is not symmetric, Cholesky does not apply
+    NotSymmetric,
+    // The diagonal in column `i` turns negative or vanishes during Cholesky factorization
+    NotPositiveDefinite(usize),
+}
+
+impl fmt::Display for SolverError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SolverError::DimensionMismatch => write!(f, "the matrix and right hand side dimensions do not match"),
+            SolverError::Singular(i) => write!(f, "the matrix is singular at column {}", i),
+            SolverError::IllConditioned(i) => write!(f, "the matrix is ill-conditioned at column {}", i),
+            SolverError::NotSymmetric => write!(f, "the matrix is not symmetric"),
+            SolverError::NotPositiveDefinite(i) => write!(f, "the matrix is not positive definite at column {}", i),
+        }
+    }
+}
+
+impl std::error::Error for SolverError {}
+
+// Largest absolute entry of the matrix
+pub(crate) fn scale(matrix: &[Vec<f64>]) -> f64 {
+    matrix.iter().flatten().fold(0.0, |max: f64, value| max.max(value.abs()))
+}
+
+// Checks a pivot against the scale of its own row before elimination, as if every row had been
+// scaled to the same size, so a stiff spring next to a slender member loses no precision by itself
+pub(crate) fn check_pivot(pivot: f64, scale: f64, n: usize, column: usize) -> Result<(), SolverError> {
+    if pivot.abs() <= f64::EPSILON * n as f64 * scale {
+        Err(SolverError::Singular(column))
+    } else if pivot.abs() < 1e-10 * scale {
+        Err(SolverError::IllConditioned(column))
+    } else {
+        Ok(())
+    }
+}
+
+// Square matrix of the same order as the right hand side
+pub(crate) fn check_dimensions(matrix: &[Vec<f64>], rhs_len: usize) -> Result<(), SolverError> {
+    if matrix.len() != rhs_len || matrix.iter().any(|row| row.len() != rhs_len) {
+        return Err(SolverError::DimensionMismatch);
+    }
+    Ok(())
+}
@@ -0,0 +1,97 @@
+use super::error::{check_dimensions, check_pivot, SolverError};
+
+// LU factorization with partial pivoting, PA = LU with the unit lower and upper factors stored in
+// one matrix
+#[derive(Debug, Clone)]
+pub struct Lu {
+    factors: Vec<Vec<f64>>,
+    permutation: Vec<usize>,
+}
+
+impl Lu {
+    pub fn factorize(mut matrix: Vec<Vec<f64>>) -> Result<Self, SolverError> {
+        let n = matrix.len();
+        check_dimensions(&matrix, n)?;
+        let scales: Vec<f64> = matrix.iter().map(|row| row.iter().fold(0.0, |max: f64, value| max.max(value.abs()))).collect();
+        let mut permutation: Vec<usize> = (0..n).collect();
+
+        for col in 0..n {
+            let pivot = (col..n)
+                .max_by(|&a, &b| matrix[a][col].abs().total_cmp(&matrix[b][col].abs()))
+                .unwrap_or(col);
+            check_pivot(matrix[pivot][col], scales[permutation[pivot]], n, col)?;
+            matrix.swap(col, pivot);
+            permutation.swap(col, pivot);
+
+            let (upper, lower) = matrix.split_at_mut(col + 1);
+            let pivot_row = &upper[col];
+            for row in lower.iter_mut() {
+                let factor = row[col] / pivot_row[col];
+                row[col] = factor;
+                for (value, pivot_value) in row[col + 1..].iter_mut().zip(&pivot_row[col + 1..]) {
+                    *value -= factor * pivot_value;
+                }
+            }
+        }
+        Ok(Lu { factors: matrix, permutation })
+    }
+
+    pub fn solve(&self, rhs: &[f64]) -> Result<Vec<f64>, SolverError> {
+        let n = self.factors.len();
+        if rhs.len() != n {
+            return Err(SolverError::DimensionMismatch);
+        }
+
+        // Forward substitution through L, then back substitution through U
+        let mut x: Vec<f64> = self.permutation.iter().map(|&i| rhs[i]).collect();
+        for row in 0..n {
+            x[row] -= (0..row).map(|k| self.factors[row][k] * x[k]).sum::<f64>();
+        }
+        for row in (0..n).rev() {
+            let sum = (row + 1..n).fold(x[row], |accum, k| accum - self.factors[row][k] * x[k]);
+            x[row] = sum / self.factors[row][row];
+        }
+        Ok(x)
+    }
+}
+
+// Solves `matrix * x = rhs` for a general square matrix
+pub fn solve(matrix: Vec<Vec<f64>>, rhs: Vec<f64>) -> Result<Vec<f64>, SolverError> {
+    check_dimensions(&matrix, rhs.len())?;
+    Lu::factorize(matrix)?.solve(&rhs)
+}
+
+#[cfg(test)]
+mod lu_tests {
+    use super::*;
+
+    #[test]
+    fn test_solve_requires_pivoting() {
+        let matrix = vec![vec![0.0, 2.0], vec![3.0, 1.0]];
+        let x = solve(matrix, vec![4.0, 5.0]).unwrap();
+        assert!((x[0] - 1.0).abs() < 1e-12);
+        assert!((x[1] - 2.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_factorization_reused_for_several_loads() {
+        let matrix = vec![vec![2.0, 1.0, 1.0], vec![4.0, -6.0, 0.0], vec![-2.0, 7.0, 2.0]];
+        let lu = Lu::factorize(matrix).unwrap();
+        assert_eq!(vec![1.0, 1.0, 2.0], lu.solve(&[5.0, -2.0, 9.0]).unwrap());
+        let x = lu.solve(&[1.0, 4.0, -2.0]).unwrap();
+        assert!((2.0 * x[0] + x[1] + x[2] - 1.0).abs() < 1e-12);
+        assert!((4.0 * x[0] - 6.0 * x[1] - 4.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_singular_and_ill_conditioned_matrices() {
+        let matrix = vec![vec![1.0, 2.0], vec![2.0, 4.0]];
+        assert_eq!(Err(SolverError::Singular(1)), solve(matrix, vec![1.0, 2.0]));
+        let matrix = vec![vec![1.0, 1.0], vec![1.0, 1.0 + 1e-12]];
+        assert_eq!(Err(SolverError::IllConditioned(1)), solve(matrix, vec![1.0, 2.0]));
+        let matrix = vec![vec![1e12, 1.0], vec![1.0, 1.0]];
+        assert!(solve(matrix, vec![1e12, 1.0]).is_ok());
+        let matrix = vec![vec![1.0, 2.0]];
+        assert_eq!(Err(SolverError::DimensionMismatch), solve(matrix, vec![1.0]));
+    }
+}
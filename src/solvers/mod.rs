@@ -0,0 +1,5 @@
+// Direct solvers for dense systems. Each factors its matrix once and solves for as many right
+// hand sides as needed
+pub mod cholesky;
+pub mod error;
+pub mod lu;
@@ -0,0 +1,31 @@
+// Internal forces at a station along a member
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DiagramPoint {
+    pub x: f64,
+    // Shear force, positive when the part left of x is pushed up
+    pub shear: f64,
+    // Bending moment, sagging positive
+    pub moment: f64,
+}
+
+// Elastic curve at a station along a member
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DeflectionPoint {
+    pub x: f64,
+    // Slope of the elastic curve, clockwise positive
+    pub slope: f64,
+    // Deflection, downward positive
+    pub deflection: f64,
+}
+
+// Stations evenly spaced over [0, length] merged with the given extra stations, sorted and deduplicated
+pub fn stations(length: f64, points: usize, extra: impl IntoIterator<Item = f64>) -> Vec<f64> {
+    let divisions = points.max(2) - 1;
+    let mut stations: Vec<f64> = (0..=divisions)
+        .map(|i| length * i as f64 / divisions as f64)
+        .chain(extra.into_iter().filter(|x| (0.0..=length).contains(x)))
+        .collect();
+    stations.sort_by(f64::total_cmp);
+    stations.dedup_by(|a, b| (*a - *b).abs() < 1e-12);
+    stations
+}
@@ -1,2 +1,3 @@
+pub mod diagram;
 pub mod span;
 pub mod traits;
\ No newline at end of file
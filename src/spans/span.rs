@@ -1,8 +1,11 @@
 use crate::{
-    loads::{distributed_load::DistributedLoad, load_traits::LoadTraits, point_load::PunctualLoad},
+    loads::{distributed_load::DistributedLoad, load_traits::LoadTraits, moment_load::MomentLoad, point_load::PunctualLoad},
     nodes::node::{Node, NodeTraits},
 };
-use super::traits::{SpanBuilder, SpanDetails, SpanTraits};
+use super::{
+    diagram::{self, DeflectionPoint, DiagramPoint},
+    traits::{SpanBuilder, SpanDetails, SpanTraits},
+};
 
 #[derive(Debug, Clone)]
 pub struct Span {
@@ -12,6 +15,28 @@ pub struct Span {
     ei: f64,
     distributed_loads: Vec<DistributedLoad>,
     punctual_loads: Vec<PunctualLoad>,
+    moment_loads: Vec<MomentLoad>,
+}
+
+impl Span {
+    fn loads(&self) -> impl Iterator<Item = &dyn LoadTraits> {
+        self.punctual_loads.iter().map(|load| load as &dyn LoadTraits)
+            .chain(self.distributed_loads.iter().map(|load| load as &dyn LoadTraits))
+            .chain(self.moment_loads.iter().map(|load| load as &dyn LoadTraits))
+    }
+
+    // Whether every load starts and ends within the span
+    pub(crate) fn loads_fit(&self) -> bool {
+        let within = |x: f64| (0.0..=self.length + 1e-12).contains(&x);
+        self.distributed_loads.iter().all(|load| load.fits(self.length))
+            && self.punctual_loads.iter().all(|load| within(load.x))
+            && self.moment_loads.iter().all(|load| within(load.x))
+    }
+
+    // Positions of the concentrated loads, where the diagrams jump
+    fn load_positions(&self) -> impl Iterator<Item = f64> + '_ {
+        self.punctual_loads.iter().map(|load| load.x).chain(self.moment_loads.iter().map(|load| load.x))
+    }
 }
 
 impl SpanDetails for Span {
@@ -19,6 +44,36 @@ impl SpanDetails for Span {
         self.length
     }
 
+    fn get_direction(&self) -> [f64; 2] {
+        let dx = self.end_node.get_x() - self.start_node.get_x();
+        let dy = self.end_node.get_y() - self.start_node.get_y();
+        [dx / self.length, dy / self.length]
+    }
+
+    fn get_ei(&self) -> f64 {
+        self.ei
+    }
+
+    fn get_start_node(&self) -> Node {
+        self.start_node
+    }
+
+    fn get_end_node(&self) -> Node {
+        self.end_node
+    }
+
+    fn get_distributed_loads(&self) -> &[DistributedLoad] {
+        &self.distributed_loads
+    }
+
+    fn get_punctual_loads(&self) -> &[PunctualLoad] {
+        &self.punctual_loads
+    }
+
+    fn get_moment_loads(&self) -> &[MomentLoad] {
+        &self.moment_loads
+    }
+
     fn get_est_a(&self) -> crate::nodes::node::SupportType {
         self.start_node.get_support()
     }
@@ -30,60 +85,185 @@ impl SpanDetails for Span {
 
 impl SpanTraits for Span {
     fn mem_a(&self) -> f64 {
-        let base_mem = 2.0 * self.ei * (
-            2.0 * self.start_node.get_deflection() + self.end_node.get_deflection()
-            - 3.0 * (self.start_node.get_rotation() - self.end_node.get_rotation()) / self.length
-        ) / self.length;
-
-        let punctual_mem = self.punctual_loads.iter().fold(0.0, |accum, punctual_load| {
-            accum + punctual_load.fem_a(self)
-        });
+        2.0 * self.ei * (
+            2.0 * self.start_node.get_rotation() + self.end_node.get_rotation()
+            - 3.0 * self.chord_rotation()
+        ) / self.length + self.fixed_fem_a()
+    }
 
-        let distributed_mem = self.distributed_loads.iter().fold(0.0, |accum, load| {
-            accum + load.fem_a(self)
-        });
+    fn mem_b(&self) -> f64 {
+        2.0 * self.ei * (
+            self.start_node.get_rotation() + 2.0 * self.end_node.get_rotation()
+            - 3.0 * self.chord_rotation()
+        ) / self.length + self.fixed_fem_b()
+    }
 
-        base_mem + punctual_mem + distributed_mem
+    fn chord_rotation(&self) -> f64 {
+        (self.end_node.get_deflection() - self.start_node.get_deflection()) / self.length
     }
 
-    fn mem_b(&self) -> f64 {
-        let base_mem = 2.0 * self.ei * (
-            self.start_node.get_deflection() + 2.0 * self.end_node.get_deflection()
-            - 3.0 * (self.start_node.get_rotation() - self.end_node.get_rotation()) / self.length
-        ) / self.length;
+    fn fixed_fem_a(&self) -> f64 {
+        self.loads().fold(0.0, |accum, load| accum + load.fixed_fem_a(self))
+    }
 
-        let punctual_mem = self.punctual_loads.iter().fold(0.0, |accum, punctual_load| {
-            accum + punctual_load.fem_b(self)
-        });
+    fn fixed_fem_b(&self) -> f64 {
+        self.loads().fold(0.0, |accum, load| accum + load.fixed_fem_b(self))
+    }
 
-        let distributed_mem = self.distributed_loads.iter().fold(0.0, |accum, load| {
-            accum + load.fem_b(self)
-        });
+    fn modified_fem(&self) -> [f64; 2] {
+        self.loads().fold([0.0, 0.0], |accum, load| [accum[0] + load.fem_a(self), accum[1] + load.fem_b(self)])
+    }
 
-        base_mem + punctual_mem + distributed_mem
+    fn simple_reactions(&self) -> [f64; 2] {
+        self.loads().fold([0.0, 0.0], |accum, load| {
+            [accum[0] + load.simple_reaction_a(self), accum[1] + load.simple_reaction_b(self)]
+        })
     }
 
     fn mes_a(&self) -> f64 {
-        todo!()
+        self.simple_reactions()[0] - (self.mem_a() + self.mem_b()) / self.length
     }
 
     fn mes_b(&self) -> f64 {
-        todo!()
+        self.simple_reactions()[1] + (self.mem_a() + self.mem_b()) / self.length
+    }
+
+    fn shear_at(&self, x: f64) -> f64 {
+        self.mes_a() - self.loads().fold(0.0, |accum, load| accum + load.force_up_to(self, x))
+    }
+
+    fn shear_before(&self, x: f64) -> f64 {
+        self.mes_a() - self.loads().fold(0.0, |accum, load| accum + load.force_before(self, x))
+    }
+
+    fn moment_at(&self, x: f64) -> f64 {
+        self.mem_a() + self.mes_a() * x - self.loads().fold(0.0, |accum, load| accum + load.moment_up_to(self, x))
+    }
+
+    fn moment_before(&self, x: f64) -> f64 {
+        self.mem_a() + self.mes_a() * x - self.loads().fold(0.0, |accum, load| accum + load.moment_before(self, x))
+    }
+
+    fn diagram(&self, points: usize) -> Vec<DiagramPoint> {
+        let stations = diagram::stations(self.length, points, self.load_positions());
+        self.diagram_at(&stations)
+    }
+
+    fn slope_at(&self, x: f64) -> f64 {
+        let load_term = self.loads().fold(0.0, |accum, load| accum + load.slope_up_to(self, x));
+        let moment_area = self.mem_a() * x + self.mes_a() * x.powi(2) / 2.0 - load_term;
+        self.start_node.get_rotation() - moment_area / self.ei
+    }
+
+    fn deflection_at(&self, x: f64) -> f64 {
+        let load_term = self.loads().fold(0.0, |accum, load| accum + load.deflection_up_to(self, x));
+        let moment_area = self.mem_a() * x.powi(2) / 2.0 + self.mes_a() * x.powi(3) / 6.0 - load_term;
+        self.start_node.get_deflection() + self.start_node.get_rotation() * x - moment_area / self.ei
+    }
+
+    fn deflection_curve(&self, points: usize) -> Vec<DeflectionPoint> {
+        let stations = diagram::stations(self.length, points, self.load_positions());
+        self.deflection_curve_at(&stations)
+    }
+
+    fn max_deflection(&self) -> DeflectionPoint {
+        let curve = self.deflection_curve(101);
+        let peak = (0..curve.len())
+            .max_by(|&i, &j| curve[i].deflection.abs().total_cmp(&curve[j].deflection.abs()))
+            .unwrap_or(0);
+        if peak == 0 || peak == curve.len() - 1 {
+            return curve[peak];
+        }
+
+        // The slope changes sign on one side of the sampled peak, bisect it there
+        let (mut low, mut high) = if curve[peak - 1].slope * curve[peak].slope <= 0.0 {
+            (curve[peak - 1].x, curve[peak].x)
+        } else {
+            (curve[peak].x, curve[peak + 1].x)
+        };
+        if self.slope_at(low) * self.slope_at(high) > 0.0 {
+            return curve[peak];
+        }
+        for _ in 0..60 {
+            let middle = (low + high) / 2.0;
+            if self.slope_at(low) * self.slope_at(middle) <= 0.0 {
+                high = middle;
+            } else {
+                low = middle;
+            }
+        }
+        let x = (low + high) / 2.0;
+        DeflectionPoint { x, slope: self.slope_at(x), deflection: self.deflection_at(x) }
     }
 }
 
 impl SpanBuilder for Span {
     fn new(start_node: Node, end_node: Node, distributed_loads: Vec<DistributedLoad>, point_loads: Vec<PunctualLoad>) -> Self {
-        let length = end_node.get_x() - start_node.get_x();
+        let length = (end_node.get_x() - start_node.get_x()).hypot(end_node.get_y() - start_node.get_y());
         Span {
             start_node,
             end_node,
             length,
             distributed_loads,
             punctual_loads: point_loads,
+            moment_loads: vec![],
             ei: 1.0,
         }
     }
+
+    fn with_moment_loads(self, moment_loads: Vec<MomentLoad>) -> Self {
+        Span { moment_loads, ..self }
+    }
+
+    fn with_loads(self, distributed_loads: Vec<DistributedLoad>, point_loads: Vec<PunctualLoad>) -> Self {
+        Span { distributed_loads, punctual_loads: point_loads, moment_loads: vec![], ..self }
+    }
+
+    fn with_ei(self, ei: f64) -> Self {
+        Span { ei, ..self }
+    }
+
+    fn with_section(self, modulus_of_elasticity: f64, moment_of_inertia: f64) -> Self {
+        self.with_ei(modulus_of_elasticity * moment_of_inertia)
+    }
+
+    fn set_nodes(&mut self, start_node: Node, end_node: Node) {
+        self.start_node = start_node;
+        self.end_node = end_node;
+    }
+
+    fn split_at(&self, node: Node) -> [Span; 2] {
+        let x = (node.get_x() - self.start_node.get_x()).hypot(node.get_y() - self.start_node.get_y());
+        let mut left = Span::new(self.start_node, node, vec![], vec![]).with_ei(self.ei);
+        let mut right = Span::new(node, self.end_node, vec![], vec![]).with_ei(self.ei);
+
+        for load in &self.punctual_loads {
+            if load.x <= x {
+                left.punctual_loads.push(*load);
+            } else {
+                right.punctual_loads.push(PunctualLoad { x: load.x - x, ..*load });
+            }
+        }
+        for load in &self.moment_loads {
+            if load.x <= x {
+                left.moment_loads.push(*load);
+            } else {
+                right.moment_loads.push(MomentLoad { x: load.x - x, ..*load });
+            }
+        }
+        for load in &self.distributed_loads {
+            if load.xf <= x {
+                left.distributed_loads.push(*load);
+            } else if load.x0 >= x {
+                right.distributed_loads.push(DistributedLoad { x0: load.x0 - x, xf: load.xf - x, ..*load });
+            } else {
+                let value = load.start_value + (load.end_value - load.start_value) * (x - load.x0) / (load.xf - load.x0);
+                left.distributed_loads.push(DistributedLoad { end_value: value, xf: x, ..*load });
+                right.distributed_loads.push(DistributedLoad { start_value: value, x0: 0.0, xf: load.xf - x, ..*load });
+            }
+        }
+        [left, right]
+    }
 }
 
 #[cfg(test)]
@@ -115,4 +295,190 @@ mod punctual_load_test {
         assert_eq!(-20.0, span.mem_a());
         assert_eq!(20.0, span.mem_b());
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_mem_uses_node_rotations() {
+        use super::*;
+        let start_node = Node::new(0.0, 0.0, SupportType::Fixed);
+        let mut end_node = Node::new(4.0, 0.0, SupportType::Hinged);
+        end_node.set_rotation(2.0);
+
+        let span = Span::new(start_node, end_node, vec![], vec![]);
+        assert_eq!(1.0, span.mem_a());
+        assert_eq!(2.0, span.mem_b());
+    }
+
+    #[test]
+    fn test_end_shears_with_fixed_ends() {
+        use super::*;
+        let load = PunctualLoad { value: 40.0, x: 3.0 };
+
+        let start_node = Node::new(0.0, 0.0, SupportType::Fixed);
+        let end_node = Node::new(12.0, 0.0, SupportType::Fixed);
+
+        let span = Span::new(start_node, end_node, vec![], vec![load]);
+        // Pb²(3a + b)/L³ and Pa²(a + 3b)/L³
+        assert!((span.mes_a() - 33.75).abs() < 1e-9);
+        assert!((span.mes_b() - 6.25).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_end_shears_with_end_moments() {
+        use super::*;
+        let start_node = Node::new(0.0, 0.0, SupportType::Fixed);
+        let mut end_node = Node::new(4.0, 0.0, SupportType::Hinged);
+        end_node.set_rotation(8.0);
+
+        let span = Span::new(start_node, end_node, vec![], vec![]);
+        assert_eq!([-3.0, 3.0], span.mes());
+    }
+
+    #[test]
+    fn test_moment_diagram_with_fixed_ends() {
+        use super::*;
+        let start_node = Node::new(0.0, 0.0, SupportType::Fixed);
+        let end_node = Node::new(6.0, 0.0, SupportType::Fixed);
+        let load = DistributedLoad { start_value: 20.0, end_value: 20.0, x0: 0.0, xf: 6.0 };
+
+        let span = Span::new(start_node, end_node, vec![load], vec![]);
+        assert!((span.moment_at(0.0) + 60.0).abs() < 1e-9);
+        assert!((span.moment_at(3.0) - 30.0).abs() < 1e-9); // wL²/24
+        assert!((span.moment_at(6.0) + 60.0).abs() < 1e-9);
+        assert!((span.shear_at(3.0)).abs() < 1e-9);
+        assert!((span.shear_at(6.0) + 60.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_shear_jump_under_point_load() {
+        use super::*;
+        let start_node = Node::new(0.0, 0.0, SupportType::Fixed);
+        let end_node = Node::new(4.0, 0.0, SupportType::Fixed);
+        let load = PunctualLoad { value: 40.0, x: 1.5 };
+
+        let span = Span::new(start_node, end_node, vec![], vec![load]);
+        let points = span.diagram(5);
+        let stations: Vec<f64> = points.iter().map(|point| point.x).collect();
+        assert_eq!(vec![0.0, 1.0, 1.5, 1.5, 2.0, 3.0, 4.0], stations);
+
+        assert!((points[2].shear - span.mes_a()).abs() < 1e-9);
+        assert!((points[3].shear - (span.mes_a() - 40.0)).abs() < 1e-9);
+        assert_eq!(points[2].moment, points[3].moment);
+        assert!((points[6].moment + span.mem_b()).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_deflection_of_fixed_beam_with_udl() {
+        use super::*;
+        let start_node = Node::new(0.0, 0.0, SupportType::Fixed);
+        let end_node = Node::new(6.0, 0.0, SupportType::Fixed);
+        let load = DistributedLoad { start_value: 20.0, end_value: 20.0, x0: 0.0, xf: 6.0 };
+
+        let span = Span::new(start_node, end_node, vec![load], vec![]);
+        assert!((span.deflection_at(6.0)).abs() < 1e-9);
+        assert!((span.slope_at(6.0)).abs() < 1e-9);
+
+        // wL⁴/384EI at midspan
+        let peak = span.max_deflection();
+        assert!((peak.x - 3.0).abs() < 1e-9);
+        assert!((peak.deflection - 67.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_deflection_with_end_rotations() {
+        use super::*;
+        let mut start_node = Node::new(0.0, 0.0, SupportType::Hinged);
+        let mut end_node = Node::new(4.0, 0.0, SupportType::Roller);
+        // End rotations of a simply supported span under a central load PL²/16EI
+        start_node.set_rotation(40.0);
+        end_node.set_rotation(-40.0);
+        let load = PunctualLoad { value: 40.0, x: 2.0 };
+
+        let span = Span::new(start_node, end_node, vec![], vec![load]);
+        assert!((span.mem_a()).abs() < 1e-9);
+        assert!((span.deflection_at(4.0)).abs() < 1e-9);
+
+        // PL³/48EI under the load
+        let peak = span.max_deflection();
+        assert!((peak.x - 2.0).abs() < 1e-9);
+        assert!((peak.deflection - 160.0 / 3.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_section_scales_stiffness_and_deflection() {
+        use super::*;
+        let start_node = Node::new(0.0, 0.0, SupportType::Fixed);
+        let mut end_node = Node::new(4.0, 0.0, SupportType::Hinged);
+        end_node.set_rotation(0.001);
+        let load = PunctualLoad { value: 40.0, x: 2.0 };
+
+        let flexible = Span::new(start_node, end_node, vec![], vec![load]);
+        let stiff = Span::new(start_node, end_node, vec![], vec![load]).with_section(200e6, 5e-5);
+        assert_eq!(10000.0, stiff.get_ei());
+        assert!((stiff.mem_b() - 20.0 - 10.0).abs() < 1e-9);
+        assert!((flexible.mem_b() - 20.0 - 0.001).abs() < 1e-9);
+
+        let fixed = Span::new(start_node, Node::new(4.0, 0.0, SupportType::Fixed), vec![], vec![load]);
+        let relative = fixed.clone().with_ei(2.0);
+        assert!((relative.deflection_at(2.0) - fixed.deflection_at(2.0) / 2.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_settlement_of_fixed_ended_span() {
+        use super::*;
+        let start_node = Node::new(0.0, 0.0, SupportType::Fixed);
+        let end_node = Node::new(4.0, 0.02, SupportType::Fixed);
+
+        let span = Span::new(start_node, end_node, vec![], vec![]).with_ei(1000.0);
+        assert_eq!(0.005, span.chord_rotation());
+        // -6EIΔ/L² at both ends
+        assert!((span.mem_a() + 7.5).abs() < 1e-9);
+        assert!((span.mem_b() + 7.5).abs() < 1e-9);
+        assert!((span.mes_a() - 3.75).abs() < 1e-9);
+        assert!((span.deflection_at(4.0) - 0.02).abs() < 1e-12);
+        assert!((span.deflection_at(2.0) - 0.01).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_split_keeps_loads_in_place() {
+        use super::*;
+        let start_node = Node::new(2.0, 0.0, SupportType::Fixed);
+        let end_node = Node::new(8.0, 0.0, SupportType::Fixed);
+        let span = Span::new(start_node, end_node, vec![DistributedLoad::new(0.0, 12.0, 0.0, 6.0).unwrap()], vec![PunctualLoad { value: 10.0, x: 5.0 }])
+            .with_ei(3.0);
+
+        let [left, right] = span.split_at(Node::new(6.0, 0.0, SupportType::Free));
+        assert_eq!(4.0, left.get_length());
+        assert_eq!(2.0, right.get_length());
+        assert_eq!(3.0, right.get_ei());
+        assert_eq!(8.0, left.get_distributed_loads()[0].end_value);
+        assert_eq!(8.0, right.get_distributed_loads()[0].start_value);
+        assert_eq!(1.0, right.get_punctual_loads()[0].x);
+        assert!(left.get_punctual_loads().is_empty());
+
+        let total = |span: &Span| span.simple_reactions().iter().sum::<f64>();
+        assert!((total(&left) + total(&right) - total(&span)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_length_of_inclined_span() {
+        use super::*;
+        let start_node = Node::new(0.0, 0.0, SupportType::Fixed);
+        let end_node = Node::at(3.0, 4.0, SupportType::Fixed);
+        let span = Span::new(start_node, end_node, vec![], vec![]);
+        assert_eq!(5.0, span.get_length());
+        assert_eq!([0.6, 0.8], span.get_direction());
+    }
+
+    #[test]
+    fn test_with_loads_replaces_couples() {
+        use super::*;
+        let start_node = Node::new(0.0, 0.0, SupportType::Fixed);
+        let end_node = Node::new(6.0, 0.0, SupportType::Fixed);
+        let span = Span::new(start_node, end_node, vec![], vec![PunctualLoad { value: 10.0, x: 3.0 }])
+            .with_moment_loads(vec![MomentLoad { value: 5.0, x: 2.0 }])
+            .with_loads(vec![DistributedLoad::uniform(4.0, 0.0, 6.0).unwrap()], vec![]);
+        assert!(span.get_moment_loads().is_empty());
+        assert!(span.get_punctual_loads().is_empty());
+        assert_eq!(1, span.get_distributed_loads().len());
+    }
+}
@@ -1,12 +1,40 @@
-use crate::{loads::{distributed_load::DistributedLoad, load_traits::LoadTraits, point_load::PunctualLoad}, nodes::node::{Node, SupportType}};
+use super::diagram::{DeflectionPoint, DiagramPoint};
+use crate::{loads::{distributed_load::DistributedLoad, load_traits::LoadTraits, moment_load::MomentLoad, point_load::PunctualLoad}, nodes::node::{Node, SupportType}};
 
 
 pub trait SpanBuilder {
     fn new(start_node: Node, end_node: Node, distributed_loads: Vec<DistributedLoad>, point_loads: Vec<PunctualLoad>) -> Self;
+
+    // Concentrated couples applied along the span
+    fn with_moment_loads(self, moment_loads: Vec<MomentLoad>) -> Self;
+
+    // Replace the loads with these distributed and point loads and no couples, keeping the nodes and EI
+    fn with_loads(self, distributed_loads: Vec<DistributedLoad>, point_loads: Vec<PunctualLoad>) -> Self;
+
+    // Flexural rigidity EI of the span, relative values such as 2.0 for "2I" are fine
+    // as long as every span of the member uses the same reference
+    fn with_ei(self, ei: f64) -> Self;
+
+    // Flexural rigidity from the modulus of elasticity and the second moment of area
+    fn with_section(self, modulus_of_elasticity: f64, moment_of_inertia: f64) -> Self;
+
+    // Replace the end nodes, used by an analysis to write back solved rotations
+    fn set_nodes(&mut self, start_node: Node, end_node: Node);
+
+    // Split the span in two at an intermediate node, sharing out its loads and keeping its EI
+    fn split_at(&self, node: Node) -> [Self; 2] where Self: Sized;
 }
 
 pub trait SpanDetails {
     fn get_length(&self) -> f64;
+    // Cosine and sine of the angle of the span, measured anticlockwise from the x axis
+    fn get_direction(&self) -> [f64; 2];
+    fn get_ei(&self) -> f64;
+    fn get_start_node(&self) -> Node;
+    fn get_end_node(&self) -> Node;
+    fn get_distributed_loads(&self) -> &[DistributedLoad];
+    fn get_punctual_loads(&self) -> &[PunctualLoad];
+    fn get_moment_loads(&self) -> &[MomentLoad];
     fn get_est_a(&self) -> SupportType;
     fn get_est_b(&self) -> SupportType;
     fn get_est(&self) -> [SupportType; 2] {
@@ -20,7 +48,7 @@ pub trait SpanFEM {
     fn fem_b<Load: LoadTraits>(&self, load: Load) -> f64;
 }
 
-pub trait SpanTraits {
+pub trait SpanTraits: SpanDetails {
     // Member End Moment
     fn mem(&self) -> [f64; 2] {
         [self.mem_a(), self.mem_b()]
@@ -32,17 +60,89 @@ pub trait SpanTraits {
     // Member End Moment at support B
     fn mem_b(&self) -> f64;
 
+    // Chord rotation ψ = (ΔB - ΔA) / L from the settlements of the end nodes, clockwise positive
+    fn chord_rotation(&self) -> f64;
+
+    // Change of [mes_a, mem_a, mes_b, mem_b] per unit [ΔA, θA, ΔB, θB] from the slope deflection equations
+    fn stiffness(&self) -> [[f64; 4]; 4] {
+        let l = self.get_length();
+        let k = 2.0 * self.get_ei() / l;
+        let moment_a = [3.0 * k / l, 2.0 * k, -3.0 * k / l, k];
+        let moment_b = [3.0 * k / l, k, -3.0 * k / l, 2.0 * k];
+        let shear_b: [f64; 4] = std::array::from_fn(|i| (moment_a[i] + moment_b[i]) / l);
+        [shear_b.map(|value| -value), moment_a, shear_b, moment_b]
+    }
+
+    // Sum of the Fixed End Moments of every load at support A
+    fn fixed_fem_a(&self) -> f64;
+
+    // Sum of the Fixed End Moments of every load at support B
+    fn fixed_fem_b(&self) -> f64;
+
+
+    // Sum of the Fixed End Moments of every load, modified for pinned, free or guided ends
+    fn modified_fem(&self) -> [f64; 2];
+
+    // Upward reactions of every load on the span if it were simply supported
+    fn simple_reactions(&self) -> [f64; 2];
 
-    // Member End Shear
+    // Member End Shear, the upward force exerted by each support on the span
     fn mes(&self) -> [f64; 2] {
         [self.mes_a(), self.mes_b()]
     }
 
     // Member End Shear at Support A
     fn mes_a(&self) -> f64;
-    
+
     // Member End Shear at Support B
     fn mes_b(&self) -> f64;
 
-    
+    // Shear force just right of x, measured from support A
+    fn shear_at(&self, x: f64) -> f64;
+
+    // Shear force just left of x, differs from `shear_at` under a punctual load
+    fn shear_before(&self, x: f64) -> f64;
+
+    // Bending moment just right of x, sagging positive
+    fn moment_at(&self, x: f64) -> f64;
+
+    // Bending moment just left of x, differs from `moment_at` under a couple
+    fn moment_before(&self, x: f64) -> f64;
+
+    // Shear and moment at the given stations, with both sides of every jump
+    fn diagram_at(&self, stations: &[f64]) -> Vec<DiagramPoint> {
+        stations.iter().fold(Vec::new(), |mut points, &x| {
+            let before = DiagramPoint { x, shear: self.shear_before(x), moment: self.moment_before(x) };
+            let after = DiagramPoint { x, shear: self.shear_at(x), moment: self.moment_at(x) };
+            if (before.shear - after.shear).abs() > 1e-12 || (before.moment - after.moment).abs() > 1e-12 {
+                points.push(before);
+            }
+            points.push(after);
+            points
+        })
+    }
+
+    // Shear and moment at `points` evenly spaced stations plus every concentrated load position
+    fn diagram(&self, points: usize) -> Vec<DiagramPoint>;
+
+    // Slope of the elastic curve at x, clockwise positive
+    fn slope_at(&self, x: f64) -> f64;
+
+    // Deflection of the elastic curve at x, downward positive
+    fn deflection_at(&self, x: f64) -> f64;
+
+    // Slope and deflection at the given stations
+    fn deflection_curve_at(&self, stations: &[f64]) -> Vec<DeflectionPoint> {
+        stations.iter().map(|&x| DeflectionPoint {
+            x,
+            slope: self.slope_at(x),
+            deflection: self.deflection_at(x),
+        }).collect()
+    }
+
+    // Slope and deflection at `points` evenly spaced stations plus every concentrated load position
+    fn deflection_curve(&self, points: usize) -> Vec<DeflectionPoint>;
+
+    // Station of the largest deflection in absolute value
+    fn max_deflection(&self) -> DeflectionPoint;
 }
\ No newline at end of file
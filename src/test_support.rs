@@ -0,0 +1,20 @@
+use crate::{
+    nodes::node::{Node, NodeTraits, SupportType},
+    spans::{span::Span, traits::SpanBuilder},
+};
+
+pub(crate) fn assert_close(expected: f64, actual: f64) {
+    assert_close_to(expected, actual, 1e-9);
+}
+
+pub(crate) fn assert_close_to(expected: f64, actual: f64, tolerance: f64) {
+    assert!((expected - actual).abs() < tolerance, "expected {}, got {}", expected, actual);
+}
+
+// Two equal 6 m spans on simple supports
+pub(crate) fn two_spans() -> [Span; 2] {
+    let a = Node::new(0.0, 0.0, SupportType::Hinged);
+    let b = Node::new(6.0, 0.0, SupportType::Roller);
+    let c = Node::new(12.0, 0.0, SupportType::Roller);
+    [Span::new(a, b, vec![], vec![]), Span::new(b, c, vec![], vec![])]
+}